@@ -1,40 +1,57 @@
 use std::cell::{Cell, RefCell};
+use std::convert::TryFrom;
 use std::rc::Rc;
+use std::time::Instant;
 
 use anyhow::{bail, Result};
 use crossterm::event::{Event, KeyEvent};
 use log::info;
 use tui::backend::Backend;
 use tui::Frame;
-use tui::layout::{Constraint, Direction, Layout, Margin, Rect};
+use tui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use tui::text::{Span, Spans};
-use tui::widgets::{Block, Borders, Tabs};
+use unicode_width::UnicodeWidthStr;
+use tui::widgets::{Block, Borders, Paragraph, Tabs};
 
-use crate::{accessors, setup_popups};
 use crate::cmdbar::CommandBar;
-use crate::components::{CommandBlocking, CommandInfo, Component, DrawableComponent, event_pump, HelpComponent};
-use crate::keys::{KeyConfig, SharedKeyConfig};
+use crate::components::{CommandBlocking, CommandInfo, Component, EventState, action_pump, edited_pump, event_pump, CommandPaletteComponent, HelpComponent, KeysEditorComponent, ConfirmComponent, MsgComponent, MsgKind, Popup, ToastComponent};
+use crate::keys::{ActionId, KeyConfig, KeyContext, SharedKeyConfig};
 use crate::strings;
 use crate::strings::order;
-use crate::tabs::{T01, T02, T03};
+use crate::tabs::{self, LayoutConfig, Tab, TabsConfig};
+use crate::popup_stack::PopupStack;
+use crate::queue::{InternalEvent, Queue};
+use crate::session::Session;
+use crate::settings::{Settings, SharedSettings};
 use crate::ui::style::{SharedTheme, Theme};
+use crate::ui::{Dim, UiBackend};
 
 // use crate::util::{RandomSignal, SinSignal, TabsState};
 
 pub struct App {
     do_quit: bool,
     help: HelpComponent,
-    t01: T01,
-    t02: T02,
-    t03: T03,
+    keys_editor: KeysEditorComponent,
+    palette: CommandPaletteComponent,
+    confirm: ConfirmComponent,
+    msg: MsgComponent,
+    toasts: ToastComponent,
+    /// popups remember the tab and pane they took the focus from
+    popup_stack: PopupStack<(usize, Option<String>)>,
+    tabs: Vec<Box<dyn Tab>>,
+    tabs_config: TabsConfig,
     pub should_quit: bool,
     pub enhanced_graphics: bool,
     pub key_config: SharedKeyConfig,
+    settings: SharedSettings,
     cmdbar: RefCell<CommandBar>,
     theme: SharedTheme,
     pub tab: usize,
     requires_redraw: Cell<bool>,
-    // queue: Queue,
+    queue: Queue,
+    session: Option<Session>,
+    /// text waiting for main to run the external editor on
+    editor_request: Option<String>,
 }
 
 // pub enum Event<I> {
@@ -44,11 +61,17 @@ pub struct App {
 
 impl App {
     #[allow(clippy::too_many_lines)]
-    pub fn new(enhanced_graphics: bool, theme: Theme, key_config: KeyConfig) -> Self {
-        let theme = Rc::new(theme);
-        // let queue = Queue::new();
-        let key_config = Rc::new(key_config);
-
+    pub fn new(
+        enhanced_graphics: bool,
+        theme: Theme,
+        key_config: KeyConfig,
+        settings: Settings,
+    ) -> Result<Self> {
+        let theme: SharedTheme = Rc::new(theme);
+        let key_config: SharedKeyConfig =
+            Rc::new(RefCell::new(key_config));
+        let settings: SharedSettings = Rc::new(settings);
+        let queue = Queue::new();
         let mut app = App {
             // title: title,
             should_quit: false,
@@ -58,33 +81,61 @@ impl App {
             cmdbar: RefCell::new(CommandBar::new(
                 theme.clone(),
                 key_config.clone(),
+                settings.clone(),
             )),
             tab: 0,
             requires_redraw: Cell::new(false),
-            t01: T01::new(
-                // &queue,
+            tabs: Vec::new(),
+            tabs_config: TabsConfig::init(TabsConfig::get_config_file()?)
+                .unwrap_or_else(|e| {
+                    log::warn!("tabs config: {}", e);
+                    queue.push(InternalEvent::ShowMsg(
+                        MsgKind::Warning,
+                        format!("tabs config: {}", e),
+                    ));
+                    TabsConfig::default()
+                }),
+            help: HelpComponent::new(
                 theme.clone(),
                 key_config.clone(),
             ),
-            t02: T02::new(
-                // &queue,
+            keys_editor: KeysEditorComponent::new(
+                KeyConfig::get_config_file().ok(),
+                &queue,
                 theme.clone(),
                 key_config.clone(),
             ),
-            t03: T03::new(
-                // &queue,
+            palette: CommandPaletteComponent::new(
                 theme.clone(),
                 key_config.clone(),
             ),
-            help: HelpComponent::new(
+            confirm: ConfirmComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            msg: MsgComponent::new(theme.clone(), key_config.clone()),
+            toasts: ToastComponent::new(
                 theme.clone(),
                 key_config.clone(),
+                settings.clone(),
             ),
-            theme,
-            // queue,
+            popup_stack: PopupStack::default(),
+            theme: theme.clone(),
+            settings: settings.clone(),
+            queue,
+            session: None,
+            editor_request: None,
         };
-        app.set_tab(0);
-        return app;
+        for tab in
+            tabs::default_tabs(&app.queue, &theme, &key_config, &settings)
+        {
+            app.register_tab(tab);
+        }
+        app.apply_layouts();
+        app.arrange_tabs()?;
+        app.process_queue()?;
+        Ok(app)
     }
 
     // pub fn on_up(&mut self) {
@@ -96,7 +147,28 @@ impl App {
     // }
 
 
-    pub fn draw<B: Backend>(&self, f: &mut Frame<B>) -> Result<()> {
+    /// who logged in through the login dialog, if anybody did
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    /// adds a tab to the end of the tab bar
+    pub fn register_tab(&mut self, tab: Box<dyn Tab>) {
+        self.tabs.push(tab);
+    }
+
+    /// a failure to draw gets logged and shown once the next event comes in
+    pub fn draw(&self, f: &mut Frame<UiBackend>) {
+        if let Err(e) = self.draw_ui(f) {
+            log::error!("failed to draw: {:?}", e);
+            self.queue.push(InternalEvent::ShowMsg(
+                MsgKind::Error,
+                format!("failed to draw: {:#}", e),
+            ));
+        }
+    }
+
+    fn draw_ui(&self, f: &mut Frame<UiBackend>) -> Result<()> {
         let fsize = f.size();
 
         self.cmdbar.borrow_mut().refresh_width(fsize.width);
@@ -116,23 +188,13 @@ impl App {
 
         self.draw_tabs(f, chunks_main[0]);
 
-        //TODO: macro because of generic draw call
-        match self.tab {
-            // match self.tabs.index {
-            // 0 => self.status_tab.draw(f, chunks_main[1])?,
-            // 1 => self.revlog.draw(f, chunks_main[1])?,
-            // 2 => self.files_tab.draw(f, chunks_main[1])?,
-            // 3 => self.stashing_tab.draw(f, chunks_main[1])?,
-            // 4 => self.stashlist_tab.draw(f, chunks_main[1])?,
-            // 0 => self.draw_first_tab(f, chunks_main[1]),
-            // 1 => self.draw_second_tab(f, chunks_main[1]),
-            0 => self.t01.draw(f, chunks_main[1])?,
-            1 => self.t02.draw(f, chunks_main[1])?,
-            2 => self.t03.draw(f, chunks_main[1])?,
-            _ => bail!("unknown tab"),
+        match self.tabs.get(self.tab) {
+            Some(tab) => tab.draw_tab(f, chunks_main[1])?,
+            None => bail!("unknown tab"),
         };
 
         self.draw_popups(f)?;
+        self.toasts.draw_toasts(f, chunks_main[1]);
 
         Ok(())
     }
@@ -142,15 +204,23 @@ impl App {
             vertical: 0,
             horizontal: 1,
         });
+        let r = match &self.session {
+            Some(session) => self.draw_session(f, r, session),
+            None => r,
+        };
 
-        let tabs = [
-            Span::raw(strings::tab_t01(&self.key_config)),
-            Span::raw(strings::tab_t02(&self.key_config)),
-            Span::raw(strings::tab_t03(&self.key_config))
-        ]
+        let visible = self.visible_tabs();
+        let tabs = visible
             .iter()
-            .cloned()
-            .map(Spans::from)
+            .map(|i| {
+                let tab = &self.tabs[*i];
+                Spans::from(Span::raw(strings::tab_title(
+                    &self.key_config,
+                    tab.icon(),
+                    &tab.title(),
+                    self.tab_hotkey(*i),
+                )))
+            })
             .collect();
 
         f.render_widget(
@@ -163,35 +233,197 @@ impl App {
                 .style(self.theme.tab(false))
                 .highlight_style(self.theme.tab(true))
                 .divider(strings::tab_divider(&self.key_config))
-                .select(self.tab),
+                .select(
+                    visible
+                        .iter()
+                        .position(|i| *i == self.tab)
+                        .unwrap_or_default(),
+                ),
             r,
         );
     }
-    pub fn event(&mut self, ev: Event) -> Result<()> {
-        if event_pump(ev, self.components_mut().as_mut_slice())?
-            .is_consumed() {
+    /// draws the logged in user to the right end of `r`,
+    /// returns what is left for the tabs
+    fn draw_session<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        r: Rect,
+        session: &Session,
+    ) -> Rect {
+        let identity = session.identity();
+        let user = strings::session_user(
+            &self.key_config,
+            identity.name(),
+            &identity.roles,
+            session.minutes(),
+        );
+        let width = u16::try_from(user.width())
+            .unwrap_or(u16::MAX)
+            .saturating_add(1)
+            .min(r.width / 2);
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(width)].as_ref())
+            .split(r);
+
+        f.render_widget(
+            Paragraph::new(user)
+                .alignment(Alignment::Right)
+                .style(self.theme.text(true, false))
+                .block(
+                    Block::default()
+                        .borders(Borders::BOTTOM)
+                        .border_style(self.theme.block(false)),
+                ),
+            chunks[1],
+        );
+
+        chunks[0]
+    }
+
+    /// errors do not end the app, they show up in the message popup
+    pub fn event(&mut self, ev: Event) {
+        if let (Event::Key(_), Some(session)) | (Event::Mouse(_), Some(session)) =
+            (ev, &mut self.session)
+        {
+            session.touch();
+        }
+        let res = self
+            .handle_event(ev)
+            .and_then(|_| self.process_queue());
+        if let Err(e) = res {
+            log::error!("event {:?} failed: {:?}", ev, e);
+            self.msg.show_error(format!("{:#}", e));
+        }
+        self.sync_popups();
+    }
+
+    /// text an input asked the external editor for, main suspends
+    /// the terminal for it and hands the outcome to `edited`
+    pub fn take_editor_request(&mut self) -> Option<String> {
+        self.editor_request.take()
+    }
+
+    /// gives what the external editor returned to the input that asked
+    pub fn edited(&mut self, edited: Result<String>) {
+        // the editor left the screen behind in an unknown state
+        self.requires_redraw.set(true);
+        let res = edited_pump(
+            edited.as_deref().ok(),
+            self.components_mut().as_mut_slice(),
+        )
+        .and_then(|_| self.process_queue());
+        if let Err(e) = edited.and(res) {
+            log::error!("external editor failed: {:?}", e);
+            self.msg.show_error(format!("{:#}", e));
+        }
+        self.update_commands();
+        self.sync_popups();
+    }
+
+    /// queues a message to show in the message popup
+    pub fn show_msg(&mut self, kind: MsgKind, msg: String) {
+        self.msg.show(kind, msg);
+        self.sync_popups();
+    }
+
+    fn handle_event(&mut self, ev: Event) -> Result<()> {
+        // a key being captured is never a shortcut
+        if self.keys_editor.is_capturing() {
+            self.keys_editor.event(ev)?;
+            return self.process_results();
+        }
+
+        // popups never cover the command bar
+        if self.cmdbar.borrow_mut().event(ev) {
             return Ok(());
         }
 
         if let Event::Key(k) = ev {
-            if k == self.key_config.open_help {
-                self.help.show();
+            if k == self.key_config.borrow().global.open_palette
+                && !self.palette.is_visible()
+            {
+                let commands = self
+                    .commands(false)
+                    .into_iter()
+                    .filter(|c| c.enabled && c.available)
+                    .map(|c| c.text)
+                    .collect();
+                self.palette.open(commands)?;
+                self.update()?;
+                return Ok(());
+            }
+        }
+
+        self.sync_popups();
+        if let Some(top) = self.popup_stack.top() {
+            let consumed = match self.popup_target_mut(top) {
+                Some(target) => target.event(ev)?.is_consumed(),
+                None => false,
+            };
+            if consumed {
+                return self.process_results();
             }
-            if k == self.key_config.quit || k == self.key_config.exit {
+            if let Event::Key(k) = ev {
+                if k == self.key_config.borrow().popup.exit_popup {
+                    self.hide_popup(top);
+                    return Ok(());
+                }
+            }
+            if self.is_modal(top) {
+                self.check_hard_exit(ev);
+                return Ok(());
+            }
+        }
+
+        if event_pump(ev, self.components_mut().as_mut_slice())?
+            .is_consumed() {
+            return self.process_results();
+        }
+
+        if let Event::Key(k) = ev {
+            let keys = self.key_config.borrow().global.clone();
+            if k == keys.quit || k == keys.exit {
                 self.do_quit = true;
                 return Ok(());
             }
-            if k == self.key_config.tab_toggle {
-                self.toggle_tabs(false);
-                self.update();
+            if k == keys.cmd_bar_toggle {
+                self.cmdbar.borrow_mut().toggle_more();
+                return Ok(());
+            }
+            if k == keys.cmd_bar_next || k == keys.cmd_bar_prev {
+                self.cmdbar.borrow_mut().select_next(k == keys.cmd_bar_prev);
+                return Ok(());
+            }
+            if k == keys.tab_toggle || k == keys.tab_toggle_reverse {
+                self.toggle_tabs(k == keys.tab_toggle_reverse)?;
+                self.update()?;
+                return Ok(());
+            }
+            if k == keys.tab_move_left || k == keys.tab_move_right {
+                self.move_tab(k == keys.tab_move_left)?;
+                self.update()?;
+                return Ok(());
+            }
+            if k == keys.tab_hide {
+                self.hide_tab()?;
+                self.update()?;
+                return Ok(());
+            }
+            if k == keys.logout && self.session.is_some() {
+                self.logout()?;
+                return Ok(());
+            }
+            if k == keys.tab_show_all {
+                self.show_all_tabs()?;
+                self.update()?;
                 return Ok(());
             }
 
-            if k == self.key_config.tab_status
-                || k == self.key_config.tab_log
-                || k == self.key_config.tab_files
+            if let Some(tab) = (0..self.tabs.len())
+                .find(|i| self.tab_hotkey(*i) == Some(k))
             {
-                self.switch_tab(k)?;
+                self.set_tab(tab)?;
                 self.update();
                 return Ok(());
             }
@@ -204,13 +436,13 @@ impl App {
     pub fn update(&mut self) -> Result<()> {
         log::trace!("update");
 
+        self.sync_popups();
         self.update_commands();
 
         Ok(())
     }
 
     pub fn update_commands(&mut self) {
-        self.help.set_cmds(self.commands(true));
         self.cmdbar.borrow_mut().set_cmds(self.commands(false));
     }
 
@@ -259,7 +491,7 @@ impl App {
 
     fn check_hard_exit(&mut self, ev: Event) -> bool {
         if let Event::Key(e) = ev {
-            if e == self.key_config.exit {
+            if e == self.key_config.borrow().global.exit {
                 self.do_quit = true;
                 return true;
             }
@@ -268,6 +500,23 @@ impl App {
     }
 
     pub fn on_tick(&mut self) {
+        for tab in &mut self.tabs {
+            tab.on_tick();
+        }
+        let idle = self
+            .session
+            .as_ref()
+            .map(|s| s.is_idle(&self.settings.session, Instant::now()))
+            .unwrap_or(false);
+        if idle {
+            if let Err(e) = self.lock() {
+                self.msg.show_error(format!("{:#}", e));
+            }
+        }
+        if let Err(e) = self.process_queue() {
+            self.msg.show_error(format!("{:#}", e));
+        }
+        self.toasts.on_tick();
         self.update();
         // Update progress
         // self.progress += 0.001;
@@ -288,51 +537,453 @@ impl App {
 
 //private
 impl App {
-    accessors!(
-        self,
-        [
-            t01,
-            t02,
-            t03,
-            help
-        ]
-    );
-
-    setup_popups!(
-        self,
-        [
-            help
-        ]
-    );
+    fn components(&self) -> Vec<&dyn Component> {
+        let mut res: Vec<&dyn Component> =
+            vec![
+                &self.msg,
+                &self.confirm,
+                &self.palette,
+                &self.keys_editor,
+            ];
+        res.extend(self.tabs.iter().map(|t| t.as_ref() as &dyn Component));
+        res.push(&self.toasts);
+        res.push(&self.help);
+        res
+    }
 
-    fn toggle_tabs(&mut self, reverse: bool) -> Result<()> {
-        let tabs_len = self.get_tabs().len();
-        let new_tab = if reverse {
-            self.tab.wrapping_sub(1).min(tabs_len.saturating_sub(1))
+    fn components_mut(&mut self) -> Vec<&mut dyn Component> {
+        let mut res: Vec<&mut dyn Component> =
+            vec![
+                &mut self.msg,
+                &mut self.confirm,
+                &mut self.palette,
+                &mut self.keys_editor,
+            ];
+        res.extend(
+            self.tabs
+                .iter_mut()
+                .map(|t| t.as_mut() as &mut dyn Component),
+        );
+        res.push(&mut self.toasts);
+        res.push(&mut self.help);
+        res
+    }
+
+    /// the tab's own hotkey or else the number key of its
+    /// place in the tab bar, hidden tabs have none
+    fn tab_hotkey(&self, index: usize) -> Option<KeyEvent> {
+        let position =
+            self.visible_tabs().iter().position(|i| *i == index)?;
+        self.tabs[index]
+            .hotkey()
+            .or_else(|| self.key_config.borrow().global.tab_key(position))
+    }
+
+    /// indices of the tabs shown in the tab bar, never empty,
+    /// tabs that need a login are left out until somebody logged in
+    fn visible_tabs(&self) -> Vec<usize> {
+        let unlocked: Vec<usize> = (0..self.tabs.len())
+            .filter(|i| self.session.is_some() || !self.requires_login(*i))
+            .collect();
+        let visible: Vec<usize> = unlocked
+            .iter()
+            .copied()
+            .filter(|i| !self.tabs_config.is_hidden(self.tabs[*i].id()))
+            .collect();
+        if !visible.is_empty() {
+            visible
+        } else if !unlocked.is_empty() {
+            unlocked
         } else {
-            self.tab.saturating_add(1) % tabs_len
+            (0..self.tabs.len()).collect()
+        }
+    }
+
+    fn requires_login(&self, index: usize) -> bool {
+        let tab = &self.tabs[index];
+        tab.requires_auth() || self.tabs_config.requires_login(tab.id())
+    }
+
+    /// ends the session and leaves a tab that needs it
+    fn logout(&mut self) -> Result<()> {
+        self.session = None;
+        self.toasts
+            .push(MsgKind::Info, strings::msg_logged_out(&self.key_config));
+        self.leave_locked_tab()
+    }
+
+    /// ends an idle session and brings back the login dialog
+    fn lock(&mut self) -> Result<()> {
+        self.session = None;
+        self.queue.push(InternalEvent::ShowMsg(
+            MsgKind::Warning,
+            strings::msg_session_locked(
+                &self.key_config,
+                self.settings.session.idle_timeout_mins,
+            ),
+        ));
+        self.leave_locked_tab()?;
+        if let Some(tab) = (0..self.tabs.len()).find(|i| {
+            !self.requires_login(*i) && self.tabs[*i].open_login()
+        }) {
+            self.set_tab(tab)?;
+        }
+        self.update()
+    }
+
+    fn leave_locked_tab(&mut self) -> Result<()> {
+        if self.visible_tabs().contains(&self.tab) {
+            return Ok(());
+        }
+        let first = self.visible_tabs().first().copied().unwrap_or_default();
+        self.set_tab(first)?;
+        self.update()
+    }
+
+    /// replaces built in layouts with those of `layout.ron`,
+    /// an invalid file is logged and ignored as a whole
+    fn apply_layouts(&mut self) {
+        let config = LayoutConfig::get_config_file()
+            .and_then(LayoutConfig::init)
+            .and_then(|config| {
+                config.validate(&self.tabs)?;
+                Ok(config)
+            });
+
+        match config {
+            Ok(config) => {
+                for (id, layout) in config.tabs {
+                    if let Some(tab) =
+                        self.tabs.iter_mut().find(|t| t.id() == id)
+                    {
+                        tab.set_layout(layout);
+                    }
+                }
+            }
+            Err(e) => {
+                log::error!("layout config: {}", e);
+                self.queue.push(InternalEvent::ShowMsg(
+                    MsgKind::Error,
+                    format!("layout config: {:#}", e),
+                ));
+            }
+        }
+    }
+
+    /// sorts the registered tabs as configured
+    /// and selects the first visible one
+    fn arrange_tabs(&mut self) -> Result<()> {
+        let ids: Vec<&str> = self.tabs.iter().map(|t| t.id()).collect();
+        let order = self.tabs_config.arrange(&ids);
+
+        let mut tabs: Vec<Option<Box<dyn Tab>>> =
+            self.tabs.drain(..).map(Some).collect();
+        self.tabs =
+            order.into_iter().filter_map(|i| tabs[i].take()).collect();
+
+        let first = self.visible_tabs().first().copied().unwrap_or_default();
+        self.set_tab(first)
+    }
+
+    /// stores order and hidden tabs in the config dir
+    fn save_tabs(&mut self) -> Result<()> {
+        self.tabs_config.order =
+            self.tabs.iter().map(|t| t.id().to_string()).collect();
+        self.tabs_config.save(TabsConfig::get_config_file()?)
+    }
+
+    /// swaps the current tab with its visible neighbour
+    fn move_tab(&mut self, left: bool) -> Result<()> {
+        let visible = self.visible_tabs();
+        let position = visible
+            .iter()
+            .position(|i| *i == self.tab)
+            .unwrap_or_default();
+        let other = if left {
+            position.checked_sub(1)
+        } else {
+            Some(position + 1)
         };
 
-        self.set_tab(new_tab)
+        if let Some(other) = other.and_then(|p| visible.get(p)) {
+            self.tabs.swap(self.tab, *other);
+            self.tab = *other;
+            self.save_tabs()?;
+        }
+
+        Ok(())
     }
 
-    fn switch_tab(&mut self, k: KeyEvent) -> Result<()> {
-        if k == self.key_config.tab_status {
-            self.set_tab(0)?;
-        } else if k == self.key_config.tab_log {
-            self.set_tab(1)?;
-        } else if k == self.key_config.tab_files {
-            self.set_tab(2)?;
+    /// hides the current tab unless it is the last one visible
+    fn hide_tab(&mut self) -> Result<()> {
+        if self.visible_tabs().len() > 1 {
+            let id = self.tabs[self.tab].id().to_string();
+            self.toggle_tabs(false)?;
+            self.tabs_config.hidden.push(id);
+            self.save_tabs()?;
         }
-        // } else if k == self.key_config.tab_stashing {
-        //     self.set_tab(3)?;
-        // } else if k == self.key_config.tab_stashes {
-        //     self.set_tab(4)?;
-        // }
 
         Ok(())
     }
 
+    /// app wide popups followed by those of the current tab
+    fn popups(&self) -> Vec<(&'static str, &dyn Popup)> {
+        let mut res: Vec<(&'static str, &dyn Popup)> = vec![
+            ("help", &self.help),
+            ("keys_editor", &self.keys_editor),
+            ("palette", &self.palette),
+            ("confirm", &self.confirm),
+        ];
+        if let Some(tab) = self.tabs.get(self.tab) {
+            res.extend(tab.popups());
+        }
+        res.push(("notifications", &self.toasts));
+        res.push(("msg", &self.msg));
+        res
+    }
+
+    fn popups_mut(&mut self) -> Vec<(&'static str, &mut dyn Popup)> {
+        let mut res: Vec<(&'static str, &mut dyn Popup)> = vec![
+            ("help", &mut self.help),
+            ("keys_editor", &mut self.keys_editor),
+            ("palette", &mut self.palette),
+            ("confirm", &mut self.confirm),
+        ];
+        if let Some(tab) = self.tabs.get_mut(self.tab) {
+            res.extend(tab.popups_mut());
+        }
+        res.push(("notifications", &mut self.toasts));
+        res.push(("msg", &mut self.msg));
+        res
+    }
+
+    fn any_popup_visible(&self) -> bool {
+        !self.popup_stack.is_empty()
+    }
+
+    /// puts newly shown popups on top of the stack
+    /// and drops the ones that got closed
+    fn sync_popups(&mut self) {
+        let visible: Vec<&'static str> = self
+            .popups()
+            .into_iter()
+            .filter(|(_, p)| p.is_visible())
+            .map(|(name, _)| name)
+            .collect();
+        let focused = (
+            self.tab,
+            self.tabs.get(self.tab).and_then(|t| t.focused_pane()),
+        );
+        let closed = self.popup_stack.sync(&visible, &focused);
+        // input reached the tab past a popup that is not modal,
+        // whatever got focused meanwhile stays so
+        let restore = closed
+            .into_iter()
+            .find(|(name, _)| self.is_modal(name))
+            .and_then(|(_, (tab, pane))| pane.filter(|_| tab == self.tab));
+        if let (Some(pane), Some(tab)) = (restore, self.tabs.get_mut(self.tab))
+        {
+            tab.restore_focus(&pane);
+        }
+    }
+
+    fn is_modal(&self, name: &str) -> bool {
+        self.popups()
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, p)| p.is_modal())
+            .unwrap_or(true)
+    }
+
+    fn is_tab_popup(&self, name: &str) -> bool {
+        self.tabs
+            .get(self.tab)
+            .into_iter()
+            .any(|t| t.popups().iter().any(|(n, _)| *n == name))
+    }
+
+    /// what gets the input and commands of popup `name`,
+    /// popups of a tab are reached through their tab
+    fn popup_target(&self, name: &str) -> Option<&dyn Component> {
+        if self.is_tab_popup(name) {
+            return self
+                .tabs
+                .get(self.tab)
+                .map(|t| t.as_ref() as &dyn Component);
+        }
+        self.popups()
+            .into_iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, p)| p as &dyn Component)
+    }
+
+    fn popup_target_mut(&mut self, name: &str) -> Option<&mut dyn Component> {
+        if self.is_tab_popup(name) {
+            return self
+                .tabs
+                .get_mut(self.tab)
+                .map(|t| t.as_mut() as &mut dyn Component);
+        }
+        self.popups_mut()
+            .into_iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, p)| p as &mut dyn Component)
+    }
+
+    fn hide_popup(&mut self, name: &str) {
+        if let Some((_, popup)) =
+            self.popups_mut().into_iter().find(|(n, _)| *n == name)
+        {
+            popup.hide();
+        }
+    }
+
+    /// draws the open popups bottom to top,
+    /// a modal popup on top dims everything below it
+    fn draw_popups(&self, f: &mut Frame<UiBackend>) -> Result<()> {
+        let size = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(1),
+                    Constraint::Length(self.cmdbar.borrow().height()),
+                ]
+                    .as_ref(),
+            )
+            .split(f.size())[0];
+
+        let popups = self.popups();
+        let top = self.popup_stack.top();
+        for name in self.popup_stack.iter() {
+            if let Some((_, popup)) = popups.iter().find(|(n, _)| *n == name) {
+                if Some(name) == top && popup.is_modal() {
+                    f.render_widget(Dim, f.size());
+                }
+                popup.draw_popup(f, size)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// picks up what the components left for the app after an event
+    fn process_results(&mut self) -> Result<()> {
+        if let Some(key_config) = self.keys_editor.take_changed() {
+            self.apply_key_config(key_config)?;
+        }
+        if let Some(action) = self.palette.take_action() {
+            self.run_action(action)?;
+        }
+        Ok(())
+    }
+
+    fn process_queue(&mut self) -> Result<()> {
+        while let Some(ev) = self.queue.pop() {
+            match ev {
+                InternalEvent::ShowMsg(kind, msg) => {
+                    self.msg.show(kind, msg);
+                }
+                InternalEvent::Toast(kind, msg) => {
+                    self.toasts.push(kind, msg);
+                }
+                InternalEvent::LoggedIn(identity) => {
+                    self.toasts.push(
+                        MsgKind::Info,
+                        strings::msg_logged_in(
+                            &self.key_config,
+                            identity.name(),
+                        ),
+                    );
+                    self.session = Some(Session::new(identity));
+                    self.update()?;
+                }
+                InternalEvent::ConfirmAction(action) => {
+                    self.confirm.open(action)?;
+                }
+                InternalEvent::ConfirmedAction(action) => {
+                    for tab in &mut self.tabs {
+                        if tab.action_confirmed(&action) {
+                            break;
+                        }
+                    }
+                }
+                InternalEvent::OpenExternalEditor(text) => {
+                    self.editor_request = Some(text);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// swaps the bindings every component reads,
+    /// state inside the tabs stays as it is
+    fn apply_key_config(&mut self, key_config: KeyConfig) -> Result<()> {
+        *self.key_config.borrow_mut() = key_config;
+        self.toasts.push(
+            MsgKind::Success,
+            strings::toast_keys_applied(&self.key_config),
+        );
+        self.update()
+    }
+
+    /// runs an action picked in the command palette, global ones
+    /// here and the rest in the popup on top or else the components
+    fn run_action(&mut self, action: ActionId) -> Result<()> {
+        if action.context == KeyContext::Global {
+            match action.action {
+                "quit" => self.do_quit = true,
+                "tab_toggle" => self.toggle_tabs(false)?,
+                "tab_toggle_reverse" => self.toggle_tabs(true)?,
+                "tab_hide" => self.hide_tab()?,
+                "tab_show_all" => self.show_all_tabs()?,
+                "open_help" => self.help.show()?,
+                "open_notifications" => self.toasts.show()?,
+                "open_key_editor" => self.keys_editor.show()?,
+                "logout" if self.session.is_some() => self.logout()?,
+                _ => log::warn!("action {:?} has no handler", action),
+            }
+            return self.update();
+        }
+
+        self.sync_popups();
+        let state = match self.popup_stack.top() {
+            Some(top) => match self.popup_target_mut(top) {
+                Some(target) => target.run_action(action)?,
+                None => EventState::NotConsumed,
+            },
+            None => action_pump(
+                action,
+                self.components_mut().as_mut_slice(),
+            )?,
+        };
+        if !state.is_consumed() {
+            log::warn!("action {:?} has no handler", action);
+        }
+        self.process_results()
+    }
+
+    fn show_all_tabs(&mut self) -> Result<()> {
+        self.tabs_config.hidden.clear();
+        self.save_tabs()
+    }
+
+    fn toggle_tabs(&mut self, reverse: bool) -> Result<()> {
+        let visible = self.visible_tabs();
+        let tabs_len = visible.len();
+        let position = visible
+            .iter()
+            .position(|i| *i == self.tab)
+            .unwrap_or_default();
+        let new_position = if reverse {
+            position.checked_sub(1).unwrap_or(tabs_len - 1)
+        } else {
+            position.saturating_add(1) % tabs_len
+        };
+
+        self.set_tab(visible[new_position])
+    }
+
     fn set_tab(&mut self, tab: usize) -> Result<()> {
         let tabs = self.get_tabs();
         for (i, t) in tabs.into_iter().enumerate() {
@@ -349,25 +1000,27 @@ impl App {
     }
 
     fn get_tabs(&mut self) -> Vec<&mut dyn Component> {
-        vec![
-            &mut self.t01,
-            &mut self.t02,
-            &mut self.t03,
-            // &mut self.stashing_tab,
-            // &mut self.stashlist_tab,
-        ]
+        self.tabs
+            .iter_mut()
+            .map(|t| t.as_mut() as &mut dyn Component)
+            .collect()
     }
 
     fn commands(&self, force_all: bool) -> Vec<CommandInfo> {
         let mut res = Vec::new();
 
-        for c in self.components() {
-            // c.commands(&mut res, true);
-            if c.commands(&mut res, force_all)
-                != CommandBlocking::PassingOn
-                && !force_all
-            {
-                break;
+        let top = self.popup_stack.top().filter(|_| !force_all);
+        if let Some(target) = top.and_then(|top| self.popup_target(top)) {
+            target.commands(&mut res, false);
+        } else {
+            for c in self.components() {
+                // c.commands(&mut res, true);
+                if c.commands(&mut res, force_all)
+                    != CommandBlocking::PassingOn
+                    && !force_all
+                {
+                    break;
+                }
             }
         }
 
@@ -380,10 +1033,51 @@ impl App {
             )
                 .order(order::NAV),
         );
+        res.push(
+            CommandInfo::new(
+                strings::commands::toggle_tabs_reverse(&self.key_config),
+                true,
+                !self.any_popup_visible(),
+            )
+                .order(order::NAV)
+                .hidden(),
+        );
+        res.push(
+            CommandInfo::new(
+                strings::commands::tab_move(&self.key_config),
+                true,
+                !self.any_popup_visible(),
+            )
+                .order(order::RARE_ACTION)
+                .hidden(),
+        );
+        res.push(
+            CommandInfo::new(
+                strings::commands::tab_hide(&self.key_config),
+                self.visible_tabs().len() > 1,
+                !self.any_popup_visible(),
+            )
+                .order(order::RARE_ACTION)
+                .hidden(),
+        );
+        res.push(
+            CommandInfo::new(
+                strings::commands::tab_show_all(&self.key_config),
+                !self.tabs_config.hidden.is_empty(),
+                !self.any_popup_visible(),
+            )
+                .order(order::RARE_ACTION)
+                .hidden(),
+        );
         res.push(
             CommandInfo::new(
                 strings::commands::toggle_tabs_direct(
                     &self.key_config,
+                    &self
+                        .visible_tabs()
+                        .into_iter()
+                        .filter_map(|i| self.tab_hotkey(i))
+                        .collect::<Vec<_>>(),
                 ),
                 true,
                 !self.any_popup_visible(),
@@ -391,6 +1085,25 @@ impl App {
                 .order(order::NAV),
         );
 
+        res.push(
+            CommandInfo::new(
+                strings::commands::logout(&self.key_config),
+                self.session.is_some(),
+                !self.any_popup_visible(),
+            )
+                .order(order::RARE_ACTION),
+        );
+
+        res.push(
+            CommandInfo::new(
+                strings::commands::notifications_open(&self.key_config),
+                true,
+                !self.any_popup_visible(),
+            )
+                .order(order::RARE_ACTION)
+                .hidden(),
+        );
+
         res.push(
             CommandInfo::new(
                 strings::commands::quit(&self.key_config),
@@ -402,4 +1115,51 @@ impl App {
 
         res
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use crossterm::event::Event;
+
+    use super::App;
+    use crate::{keys::KeyConfig, settings::Settings, ui::style::Theme};
+
+    #[test]
+    fn test_input_passes_non_modal_popup() {
+        let mut app = App::new(
+            false,
+            Theme::default(),
+            KeyConfig::default(),
+            Settings::default(),
+        )
+        .unwrap();
+        let keys = app.key_config.borrow().clone();
+
+        app.event(Event::Key(keys.global.open_notifications));
+        assert_eq!(app.popup_stack.top(), Some("notifications"));
+
+        // the notification history leaves the login key to tab T01
+        app.event(Event::Key(keys.tab.login));
+        assert_eq!(app.popup_stack.top(), Some("login"));
+    }
+
+    #[test]
+    fn test_editor_request_round_trip() {
+        let mut app = App::new(
+            false,
+            Theme::default(),
+            KeyConfig::default(),
+            Settings::default(),
+        )
+        .unwrap();
+        let keys = app.key_config.borrow().clone();
+
+        app.event(Event::Key(keys.tab.login));
+        app.event(Event::Key(keys.text_input.open_editor));
+        assert_eq!(app.take_editor_request().as_deref(), Some(""));
+        assert_eq!(app.take_editor_request(), None);
+
+        app.edited(Err(anyhow!("editor 'vi' failed")));
+        assert_eq!(app.popup_stack.top(), Some("msg"));
+    }
+}
@@ -1,8 +1,10 @@
 // use simplelog::{Config, LevelFilter, WriteLogger};
 use std::{
+    cell::RefCell,
     env,
     fs::{self, File},
     path::PathBuf,
+    rc::Rc,
 };
 
 use anyhow::{anyhow, Result};
@@ -12,6 +14,11 @@ use clap::{
 };
 
 use crate::bug_report;
+use crate::keys::{KeyConfig, KeyExportFormat};
+use crate::queue::Queue;
+use crate::settings::Settings;
+use crate::tabs::{self, LayoutConfig};
+use crate::ui::style::Theme;
 
 pub struct CliArgs {
     pub theme: PathBuf,
@@ -41,6 +48,20 @@ pub fn process_cmdline() -> Result<CliArgs> {
                 .help("Generate a bug report")
                 .long("bugreport"),
         )
+        .arg(
+            Arg::with_name("check-config")
+                .help("Check the key config for conflicting bindings")
+                .long("check-config"),
+        )
+        .arg(
+            Arg::with_name("print-keys")
+                .help("Print the active key bindings (defaults to markdown)")
+                .long("print-keys")
+                .value_name("FORMAT")
+                .possible_values(&KeyExportFormat::NAMES)
+                .takes_value(true)
+                .min_values(0),
+        )
         .arg(
             Arg::with_name("directory")
                 .help("Set the working directory")
@@ -54,6 +75,15 @@ pub fn process_cmdline() -> Result<CliArgs> {
         bug_report::generate_bugreport();
         std::process::exit(0);
     }
+    if arg_matches.is_present("check-config") {
+        let code = if check_config()? { 0 } else { 1 };
+        std::process::exit(code);
+    }
+    if arg_matches.is_present("print-keys") {
+        let format = arg_matches.value_of("print-keys").unwrap_or("markdown");
+        print_keys(format.parse()?)?;
+        std::process::exit(0);
+    }
     if arg_matches.is_present("logging") {
         setup_logging()?;
     }
@@ -62,6 +92,7 @@ pub fn process_cmdline() -> Result<CliArgs> {
             arg_matches.value_of("directory").unwrap_or(".");
         env::set_current_dir(directory)?;
     }
+    fs::create_dir_all(get_app_config_path()?)?;
     let arg_theme =
         arg_matches.value_of("theme").unwrap_or("theme.ron");
     if get_app_config_path()?.join(arg_theme).is_file() {
@@ -75,6 +106,57 @@ pub fn process_cmdline() -> Result<CliArgs> {
     }
 }
 
+/// prints the conflict report of the key config and checks the settings
+/// and layouts, returns `false` if a file does not parse, keys conflict
+/// or the layouts are invalid, nothing gets written
+fn check_config() -> Result<bool> {
+    let settings = match Settings::init(Settings::get_config_file()?) {
+        Ok(settings) => settings,
+        Err(e) => {
+            println!("invalid settings: {}", e);
+            return Ok(false);
+        }
+    };
+    let key_config = match KeyConfig::read(KeyConfig::get_config_file()?) {
+        Ok(key_config) => key_config,
+        Err(e) => {
+            println!("invalid key config: {}", e);
+            return Ok(false);
+        }
+    };
+    let report = key_config.check_conflicts();
+
+    if report.is_empty() {
+        println!("no key binding conflicts found");
+    }
+    for line in report.lines(&key_config) {
+        println!("{}", line);
+    }
+
+    let tabs = tabs::default_tabs(
+        &Queue::new(),
+        &Rc::new(Theme::default()),
+        &Rc::new(RefCell::new(key_config)),
+        &Rc::new(settings),
+    );
+    let layouts = LayoutConfig::init(LayoutConfig::get_config_file()?)
+        .and_then(|config| config.validate(&tabs));
+    match &layouts {
+        Ok(()) => println!("layouts are valid"),
+        Err(e) => println!("invalid layout config: {}", e),
+    }
+
+    Ok(!report.has_conflicts() && layouts.is_ok())
+}
+
+/// prints the effective key bindings in `format`, nothing gets written
+fn print_keys(format: KeyExportFormat) -> Result<()> {
+    let key_config = KeyConfig::read(KeyConfig::get_config_file()?)?;
+    print!("{}", key_config.export(format)?);
+
+    Ok(())
+}
+
 fn setup_logging() -> Result<()> {
     // let mut path = get_app_cache_path()?;
     // path.push("gitui.log");
@@ -97,6 +179,8 @@ fn get_app_cache_path() -> Result<PathBuf> {
     Ok(path)
 }
 
+/// the config dir, not created here so the
+/// non-interactive modes leave the disk alone
 pub fn get_app_config_path() -> Result<PathBuf> {
     let mut path = if cfg!(target_os = "macos") {
         dirs_next::home_dir().map(|h| h.join(".config"))
@@ -106,6 +190,5 @@ pub fn get_app_config_path() -> Result<PathBuf> {
         .ok_or_else(|| anyhow!("failed to find os config dir."))?;
 
     path.push("gitui");
-    fs::create_dir_all(&path)?;
     Ok(path)
 }
@@ -0,0 +1,91 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use super::{verify_user_hash, Authenticator, Credentials, Identity};
+
+/// an apache style htpasswd file with `user:hash` lines,
+/// only bcrypt and argon2 hashes are accepted
+pub struct HtpasswdAuthenticator {
+    file: PathBuf,
+}
+
+impl HtpasswdAuthenticator {
+    pub fn new(file: PathBuf) -> Self {
+        Self { file }
+    }
+}
+
+/// the hash stored for `username`, comments and blank lines are skipped
+fn find_hash<'a>(content: &'a str, username: &str) -> Option<&'a str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .find(|(user, _)| *user == username)
+        .map(|(_, hash)| hash)
+}
+
+impl Authenticator for HtpasswdAuthenticator {
+    fn authenticate(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<Option<Identity>> {
+        let content = fs::read_to_string(&self.file).map_err(|e| {
+            anyhow!("{}: {}", self.file.to_string_lossy(), e)
+        })?;
+
+        let hash = find_hash(&content, &credentials.username);
+        if verify_user_hash(&credentials.password, hash)? {
+            Ok(Some(Identity::new(&credentials.username)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{find_hash, HtpasswdAuthenticator};
+    use crate::auth::{Authenticator, Credentials};
+
+    fn login(name: &str, password: &str) -> Credentials {
+        Credentials {
+            username: name.to_string(),
+            password: password.into(),
+        }
+    }
+
+    #[test]
+    fn test_find_hash() {
+        let content = "# users\nann:$2y$05$abc\n\nbob:$argon2id$x\n";
+
+        assert_eq!(find_hash(content, "ann"), Some("$2y$05$abc"));
+        assert_eq!(find_hash(content, "bob"), Some("$argon2id$x"));
+        assert_eq!(find_hash(content, "# users"), None);
+        assert_eq!(find_hash(content, "carl"), None);
+    }
+
+    #[test]
+    fn test_authenticate_bcrypt() {
+        let file = env::temp_dir()
+            .join(format!("htpasswd_test_{}", process::id()));
+        fs::write(
+            &file,
+            format!("ann:{}\n", bcrypt::hash("secret", 4).unwrap()),
+        )
+        .unwrap();
+        let auth = HtpasswdAuthenticator::new(file.clone());
+
+        let identity =
+            auth.authenticate(&login("ann", "secret")).unwrap().unwrap();
+        assert_eq!(identity.username, "ann");
+        assert_eq!(auth.authenticate(&login("ann", "wrong")).unwrap(), None);
+        assert_eq!(auth.authenticate(&login("bob", "secret")).unwrap(), None);
+
+        fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use ureq::{Agent, AgentBuilder};
+use url::{Host, Url};
+
+use super::{Authenticator, Credentials, Identity};
+
+/// what a successful login may tell about the user
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct Response {
+    display_name: Option<String>,
+    roles: Vec<String>,
+}
+
+/// posts the credentials as json to an endpoint,
+/// 2xx logs in, 401 and 403 mean wrong credentials
+pub struct HttpAuthenticator {
+    url: String,
+    agent: Agent,
+}
+
+impl HttpAuthenticator {
+    /// `url` has to be https, plain http is only accepted for
+    /// this machine and only with `allow_loopback_http`
+    pub fn new(
+        url: &str,
+        timeout: Duration,
+        allow_loopback_http: bool,
+    ) -> Result<Self> {
+        check_url(url, allow_loopback_http)?;
+        Ok(Self {
+            url: url.to_string(),
+            // a redirect could send the password somewhere unencrypted
+            agent: AgentBuilder::new().timeout(timeout).redirects(0).build(),
+        })
+    }
+}
+
+fn check_url(url: &str, allow_loopback_http: bool) -> Result<()> {
+    let parsed = Url::parse(url).map_err(|e| anyhow!("{}: {}", url, e))?;
+    match parsed.scheme() {
+        "https" => Ok(()),
+        "http" if allow_loopback_http && is_loopback(&parsed) => Ok(()),
+        "http" if is_loopback(&parsed) => Err(anyhow!(
+            "{}: plain http needs allow_loopback_http: true",
+            url
+        )),
+        _ => Err(anyhow!("{}: passwords are only sent over https", url)),
+    }
+}
+
+fn is_loopback(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+impl Authenticator for HttpAuthenticator {
+    fn authenticate(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<Option<Identity>> {
+        let res = self.agent.post(&self.url).send_json(serde_json::json!({
+            "username": credentials.username,
+            "password": credentials.password.expose(),
+        }));
+
+        match res {
+            Ok(res) if res.status() / 100 != 2 => Err(anyhow!(
+                "{} answered with status {}",
+                self.url,
+                res.status()
+            )),
+            Ok(res) => {
+                // an empty body is fine, the user just has no extra info
+                let body = res.into_string()?;
+                let info = if body.trim().is_empty() {
+                    Response::default()
+                } else {
+                    serde_json::from_str(&body)?
+                };
+                Ok(Some(Identity {
+                    username: credentials.username.clone(),
+                    display_name: info.display_name,
+                    roles: info.roles,
+                }))
+            }
+            Err(ureq::Error::Status(401, _))
+            | Err(ureq::Error::Status(403, _)) => Ok(None),
+            Err(ureq::Error::Status(code, _)) => {
+                Err(anyhow!("{} answered with status {}", self.url, code))
+            }
+            Err(e) => Err(anyhow!("{}: {}", self.url, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{check_url, HttpAuthenticator};
+    use crate::auth::{Authenticator, Credentials};
+
+    fn login(password: &str) -> Credentials {
+        Credentials {
+            username: "ann".to_string(),
+            password: password.into(),
+        }
+    }
+
+    /// answers `requests` posts, 200 if the body has the right password
+    fn mock_server(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/login", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    let lower = line.to_lowercase();
+                    if let Some(v) = lower.strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8(body).unwrap();
+
+                let response = if body.contains(r#""password":"secret""#) {
+                    let json = r#"{"display_name":"Ann","roles":["dev"]}"#;
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                        json.len(),
+                        json
+                    )
+                } else {
+                    "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n"
+                        .to_string()
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_http_endpoint() {
+        let url = mock_server(2);
+        let auth =
+            HttpAuthenticator::new(&url, Duration::from_secs(5), true)
+                .unwrap();
+
+        let identity = auth.authenticate(&login("secret")).unwrap().unwrap();
+        assert_eq!(identity.name(), "Ann");
+        assert_eq!(identity.roles, vec!["dev".to_string()]);
+        assert_eq!(auth.authenticate(&login("wrong")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_http_needs_https() {
+        assert!(check_url("https://example.com/login", false).is_ok());
+        assert!(check_url("http://example.com/login", true).is_err());
+        assert!(check_url("ftp://example.com/login", true).is_err());
+        assert!(check_url("http://localhost:8080/login", false).is_err());
+        assert!(check_url("http://localhost:8080/login", true).is_ok());
+        assert!(check_url("http://127.0.0.1:8080/login", true).is_ok());
+        assert!(check_url("http://[::1]:8080/login", true).is_ok());
+    }
+}
@@ -0,0 +1,118 @@
+use std::{fs::File, io::Read, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::args::get_app_config_path;
+
+use super::{verify_user_hash, Authenticator, Credentials, Identity};
+
+#[derive(Deserialize, Debug)]
+struct User {
+    username: String,
+    hash: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct CredentialsFile {
+    #[serde(default)]
+    users: Vec<User>,
+}
+
+/// users with argon2 or bcrypt hashes in a ron file,
+/// the file is read on every login so edits apply right away
+pub struct LocalAuthenticator {
+    file: PathBuf,
+}
+
+impl LocalAuthenticator {
+    pub fn new(file: PathBuf) -> Self {
+        Self { file }
+    }
+
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("credentials.ron"))
+    }
+
+    fn read_file(&self) -> Result<CredentialsFile> {
+        let mut f = File::open(&self.file).map_err(|e| {
+            anyhow!("{}: {}", self.file.to_string_lossy(), e)
+        })?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        ron::de::from_bytes(&buffer)
+            .map_err(|e| anyhow!("{}: {}", self.file.to_string_lossy(), e))
+    }
+}
+
+impl Authenticator for LocalAuthenticator {
+    fn authenticate(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<Option<Identity>> {
+        let user = self
+            .read_file()?
+            .users
+            .into_iter()
+            .find(|u| u.username == credentials.username);
+
+        let verified = verify_user_hash(
+            &credentials.password,
+            user.as_ref().map(|u| u.hash.as_str()),
+        )?;
+        let user = match user {
+            Some(user) if verified => user,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Identity {
+            username: user.username,
+            display_name: user.display_name,
+            roles: user.roles,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::LocalAuthenticator;
+    use crate::auth::{Authenticator, Credentials};
+
+    fn login(name: &str, password: &str) -> Credentials {
+        Credentials {
+            username: name.to_string(),
+            password: password.into(),
+        }
+    }
+
+    #[test]
+    fn test_local_file() {
+        let file = env::temp_dir()
+            .join(format!("credentials_test_{}.ron", process::id()));
+        fs::write(
+            &file,
+            format!(
+                r#"(users: [(username: "ann", hash: "{}", roles: ["admin"])])"#,
+                bcrypt::hash("secret", 4).unwrap()
+            ),
+        )
+        .unwrap();
+        let auth = LocalAuthenticator::new(file.clone());
+
+        let identity =
+            auth.authenticate(&login("ann", "secret")).unwrap().unwrap();
+        assert_eq!(identity.roles, vec!["admin".to_string()]);
+        assert_eq!(auth.authenticate(&login("ann", "wrong")).unwrap(), None);
+        assert_eq!(auth.authenticate(&login("bob", "secret")).unwrap(), None);
+
+        fs::remove_file(&file).unwrap();
+        assert!(auth.authenticate(&login("ann", "secret")).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use std::{fs::File, io::Read, path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use serde::{Deserialize, Serialize};
+
+use crate::args::get_app_config_path;
+
+pub use htpasswd::HtpasswdAuthenticator;
+pub use http::HttpAuthenticator;
+pub use local::LocalAuthenticator;
+pub use secret::{Credentials, Secret};
+
+mod htpasswd;
+mod http;
+mod local;
+mod secret;
+
+/// who logged in, handed to the rest of the app after a successful login
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identity {
+    pub username: String,
+    pub display_name: Option<String>,
+    pub roles: Vec<String>,
+}
+
+impl Identity {
+    pub fn new(username: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            display_name: None,
+            roles: Vec::new(),
+        }
+    }
+
+    /// the display name if the backend knows one, the username otherwise
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+}
+
+/// checks credentials against some user database,
+/// runs off the ui thread so it may block
+pub trait Authenticator: Send + Sync {
+    /// `Ok(None)` if the credentials are wrong,
+    /// errors mean the backend could not be asked
+    fn authenticate(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<Option<Identity>>;
+}
+
+/// compares `password` with an argon2 (`$argon2..`) or bcrypt (`$2b$..`) hash
+pub fn verify_hash(password: &Secret, hash: &str) -> Result<bool> {
+    let password = password.expose();
+    if hash.starts_with("$argon2") {
+        let parsed = PasswordHash::new(hash)
+            .map_err(|e| anyhow!("invalid argon2 hash: {}", e))?;
+        Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok())
+    } else if hash.starts_with("$2") {
+        Ok(bcrypt::verify(password, hash)?)
+    } else {
+        bail!("unsupported password hash, expected argon2 or bcrypt")
+    }
+}
+
+/// verified for unknown users so they take as long to reject as a
+/// wrong password, bcrypt with the default cost
+const DUMMY_HASH: &str =
+    "$2b$12$05WIJWANEI9sD88E/VH3Yuzp/zJrVQoRkt79atqyxWnAfyN8w133i";
+
+/// like `verify_hash` but checks a dummy hash when there is none,
+/// so whether a user exists does not show in the response time
+pub fn verify_user_hash(
+    password: &Secret,
+    hash: Option<&str>,
+) -> Result<bool> {
+    match hash {
+        Some(hash) => verify_hash(password, hash),
+        None => verify_hash(password, DUMMY_HASH).map(|_| false),
+    }
+}
+
+/// where credentials get checked
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AuthBackend {
+    /// `credentials.ron` next to the other config files unless `file` is set
+    Local { file: Option<PathBuf> },
+    /// an apache style `user:hash` file
+    Htpasswd { file: PathBuf },
+    /// posts `{"username", "password"}` as json to `url`,
+    /// which has to be https unless it is a loopback address
+    /// and `allow_loopback_http` is set
+    Http {
+        url: String,
+        timeout_secs: u64,
+        #[serde(default)]
+        allow_loopback_http: bool,
+    },
+}
+
+impl Default for AuthBackend {
+    fn default() -> Self {
+        Self::Local { file: None }
+    }
+}
+
+/// backend the login dialog uses, read from `auth.ron`
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub backend: AuthBackend,
+}
+
+impl AuthConfig {
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("auth.ron"))
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    /// a missing file means the local credentials file
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file.clone()).map_err(|e| {
+                anyhow!("{}: {}", file.to_string_lossy(), e)
+            })
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn authenticator(&self) -> Result<Arc<dyn Authenticator>> {
+        Ok(match &self.backend {
+            AuthBackend::Local { file } => {
+                let file = match file {
+                    Some(file) => file.clone(),
+                    None => LocalAuthenticator::get_config_file()?,
+                };
+                Arc::new(LocalAuthenticator::new(file))
+            }
+            AuthBackend::Htpasswd { file } => {
+                Arc::new(HtpasswdAuthenticator::new(file.clone()))
+            }
+            AuthBackend::Http {
+                url,
+                timeout_secs,
+                allow_loopback_http,
+            } => Arc::new(HttpAuthenticator::new(
+                url,
+                Duration::from_secs(*timeout_secs),
+                *allow_loopback_http,
+            )?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use argon2::{
+        password_hash::{PasswordHasher, SaltString},
+        Argon2,
+    };
+
+    use super::{verify_hash, AuthBackend, AuthConfig, Secret};
+
+    #[test]
+    fn test_auth_example() {
+        let config = AuthConfig::read_file("auth_example.ron".into()).unwrap();
+
+        assert_eq!(config.backend, AuthBackend::Local { file: None });
+    }
+
+    #[test]
+    fn test_verify_hash() {
+        let salt = SaltString::encode_b64(b"saltsaltsaltsalt").unwrap();
+        let argon = Argon2::default()
+            .hash_password(b"secret", &salt)
+            .unwrap()
+            .to_string();
+        let bcrypt = bcrypt::hash("secret", 4).unwrap();
+
+        let secret = Secret::from("secret");
+        for hash in &[argon, bcrypt] {
+            assert!(verify_hash(&secret, hash).unwrap());
+            assert!(!verify_hash(&Secret::from("wrong"), hash).unwrap());
+        }
+        assert!(verify_hash(&secret, "secret").is_err());
+    }
+}
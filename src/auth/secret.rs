@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// a string that never shows up in `Debug` or `Display` output,
+/// `expose` is the only way to read it
+#[derive(Clone, Default, PartialEq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+/// what the login dialog hands to an `Authenticator`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credentials {
+    pub username: String,
+    pub password: Secret,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Credentials, Secret};
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let credentials = Credentials {
+            username: "ann".to_string(),
+            password: Secret::from("hunter2"),
+        };
+
+        for shown in &[
+            format!("{:?}", credentials),
+            format!("{:#?}", credentials),
+            format!("{}", credentials.password),
+        ] {
+            assert!(!shown.contains("hunter2"));
+        }
+        assert_eq!(credentials.password.expose(), "hunter2");
+    }
+}
@@ -1,16 +1,21 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::RefCell, convert::TryFrom};
 
+use crossterm::event::Event;
 use tui::{
     backend::Backend,
     Frame,
     layout::{Alignment, Rect},
+    style::Modifier,
     text::{Span, Spans},
     widgets::Paragraph,
 };
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    components::CommandInfo, keys::SharedKeyConfig, strings,
+    components::{clicked, CommandInfo},
+    keys::SharedKeyConfig,
+    settings::SharedSettings,
+    strings,
     ui::style::SharedTheme,
 };
 
@@ -21,8 +26,11 @@ enum DrawListEntry {
 }
 
 struct Command {
+    /// position in `cmd_infos`
+    index: usize,
     txt: String,
     enabled: bool,
+    selected: bool,
     line: usize,
 }
 
@@ -32,28 +40,62 @@ pub struct CommandBar {
     cmd_infos: Vec<CommandInfo>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
+    settings: SharedSettings,
     lines: u16,
     width: u16,
     expandable: bool,
     expanded: bool,
+    /// name of the selected entry, kept while the entry is there
+    selected: Option<String>,
+    /// where entries got drawn, for mouse clicks
+    areas: RefCell<Vec<(usize, Rect)>>,
 }
 
-const MORE_WIDTH: u16 = 9;
-
 impl CommandBar {
     pub const fn new(
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        settings: SharedSettings,
     ) -> Self {
         Self {
             draw_list: Vec::new(),
             cmd_infos: Vec::new(),
             theme,
             key_config,
+            settings,
             lines: 0,
             width: 0,
             expandable: false,
             expanded: false,
+            selected: None,
+            areas: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// room reserved for the expand toggle
+    fn more_width(&self) -> u16 {
+        let w = [true, false]
+            .iter()
+            .map(|e| strings::cmd_more(&self.key_config, *e).width())
+            .max()
+            .unwrap_or_default();
+        u16::try_from(w).unwrap_or(u16::MAX).saturating_add(1)
+    }
+
+    fn is_selected(&self, c: &CommandInfo) -> bool {
+        self.selected.as_deref() == Some(c.text.name.as_str())
+    }
+
+    /// text of a command, disabled ones explain themselves
+    /// once the bar is expanded or they got selected
+    fn entry_text(&self, c: &CommandInfo) -> String {
+        match c.reason {
+            Some(reason)
+                if !c.enabled && (self.expanded || self.is_selected(c)) =>
+            {
+                strings::cmd_disabled(&self.key_config, &c.text.name, reason)
+            }
+            _ => c.text.name.clone(),
         }
     }
 
@@ -67,8 +109,7 @@ impl CommandBar {
     fn is_multiline(&self, width: u16) -> bool {
         let mut line_width = 0_usize;
         for c in &self.cmd_infos {
-            let entry_w =
-                UnicodeWidthStr::width(c.text.name.as_str());
+            let entry_w = self.entry_text(c).width();
 
             if line_width + entry_w > width as usize {
                 return true;
@@ -84,7 +125,7 @@ impl CommandBar {
         self.draw_list.clear();
 
         let width = if self.is_multiline(width) {
-            width.saturating_sub(MORE_WIDTH)
+            width.saturating_sub(self.more_width())
         } else {
             width
         };
@@ -92,9 +133,9 @@ impl CommandBar {
         let mut line_width = 0_usize;
         let mut lines = 1_u16;
 
-        for c in &self.cmd_infos {
-            let entry_w =
-                UnicodeWidthStr::width(c.text.name.as_str());
+        for (index, c) in self.cmd_infos.iter().enumerate() {
+            let txt = self.entry_text(c);
+            let entry_w = txt.width();
 
             if line_width + entry_w > width as usize {
                 self.draw_list.push(DrawListEntry::LineBreak);
@@ -107,8 +148,10 @@ impl CommandBar {
             line_width += entry_w + 1;
 
             self.draw_list.push(DrawListEntry::Command(Command {
-                txt: c.text.name.to_string(),
+                index,
+                txt,
                 enabled: c.enabled,
+                selected: self.is_selected(c),
                 line: lines.saturating_sub(1) as usize,
             }));
         }
@@ -124,12 +167,56 @@ impl CommandBar {
             .filter(CommandInfo::show_in_quickbar)
             .collect::<Vec<_>>();
         self.cmd_infos.sort_by_key(|e| e.order);
+        if !self.cmd_infos.iter().any(|c| self.is_selected(c)) {
+            self.selected = None;
+        }
         self.refresh_list(self.width);
     }
 
-    pub const fn height(&self) -> u16 {
+    /// moves the selection along the entries,
+    /// past either end nothing is selected
+    pub fn select_next(&mut self, reverse: bool) {
+        let len = self.cmd_infos.len();
+        let current = self.cmd_infos.iter().position(|c| self.is_selected(c));
+        let next = match (current, reverse) {
+            (None, false) => Some(0),
+            (None, true) => len.checked_sub(1),
+            (Some(i), false) => Some(i + 1).filter(|i| *i < len),
+            (Some(i), true) => i.checked_sub(1),
+        };
+        self.select(next);
+    }
+
+    /// a click on an entry selects it, a second one clears the selection,
+    /// `true` if the click hit an entry
+    pub fn event(&mut self, ev: Event) -> bool {
+        let hit = self
+            .areas
+            .borrow()
+            .iter()
+            .find(|(_, r)| clicked(ev, *r).is_some())
+            .map(|(i, _)| *i);
+        let again = hit
+            .and_then(|i| self.cmd_infos.get(i))
+            .is_some_and(|c| self.is_selected(c));
+        match hit {
+            Some(_) if again => self.select(None),
+            Some(i) => self.select(Some(i)),
+            None => return false,
+        }
+        true
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index
+            .and_then(|i| self.cmd_infos.get(i))
+            .map(|c| c.text.name.clone());
+        self.refresh_list(self.width);
+    }
+
+    pub fn height(&self) -> u16 {
         if self.expandable && self.expanded {
-            self.lines
+            self.lines.min(self.settings.cmd_bar_max_lines.max(1))
         } else {
             1_u16
         }
@@ -138,16 +225,54 @@ impl CommandBar {
     pub fn toggle_more(&mut self) {
         if self.expandable {
             self.expanded = !self.expanded;
+            self.refresh_list(self.width);
+        }
+    }
+
+    /// remembers the cells of every entry visible inside `r`
+    fn store_areas(&self, r: Rect, splitter_width: usize) {
+        let mut areas = self.areas.borrow_mut();
+        areas.clear();
+        let mut x = 0_usize;
+        let mut row = 0_u16;
+        for entry in &self.draw_list {
+            match entry {
+                DrawListEntry::LineBreak => {
+                    x = 0;
+                    row += 1;
+                }
+                DrawListEntry::Splitter => x += splitter_width,
+                DrawListEntry::Command(c) => {
+                    let width = c.txt.width();
+                    let left = u16::try_from(x).unwrap_or(u16::MAX);
+                    if row < r.height && left < r.width {
+                        areas.push((
+                            c.index,
+                            Rect::new(
+                                r.x + left,
+                                r.y + row,
+                                u16::try_from(width)
+                                    .unwrap_or(u16::MAX)
+                                    .min(r.width - left),
+                                1,
+                            ),
+                        ));
+                    }
+                    x += width;
+                }
+            }
         }
     }
 
     pub fn draw<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
-        if r.width < MORE_WIDTH {
+        let more_width = self.more_width();
+        if r.width < more_width {
             return;
         }
         let splitter = Span::raw(Cow::from(strings::cmd_splitter(
             &self.key_config,
         )));
+        self.store_areas(r, splitter.width());
 
         let texts = self
             .draw_list
@@ -158,11 +283,16 @@ impl CommandBar {
                         .iter()
                         .map(|c| match c {
                             DrawListEntry::Command(c) => {
+                                let style = self
+                                    .theme
+                                    .commandbar(c.enabled, c.line);
                                 Span::styled(
                                     Cow::from(c.txt.as_str()),
-                                    self.theme.commandbar(
-                                        c.enabled, c.line,
-                                    ),
+                                    if c.selected {
+                                        style.add_modifier(Modifier::REVERSED)
+                                    } else {
+                                        style
+                                    },
                                 )
                             }
                             DrawListEntry::LineBreak => {
@@ -185,19 +315,18 @@ impl CommandBar {
 
         if self.expandable {
             let r = Rect::new(
-                r.width.saturating_sub(MORE_WIDTH),
+                r.width.saturating_sub(more_width),
                 r.y + r.height.saturating_sub(1),
-                MORE_WIDTH.min(r.width),
+                more_width.min(r.width),
                 1.min(r.height),
             );
 
             f.render_widget(
                 Paragraph::new(Spans::from(vec![Span::raw(
-                    Cow::from(if self.expanded {
-                        "less [.]"
-                    } else {
-                        "more [.]"
-                    }),
+                    Cow::from(strings::cmd_more(
+                        &self.key_config,
+                        self.expanded,
+                    )),
                 )]))
                     .alignment(Alignment::Right),
                 r,
@@ -205,3 +334,49 @@ impl CommandBar {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::CommandBar;
+    use crate::{
+        components::{CommandInfo, CommandText},
+        keys::KeyConfig,
+        settings::Settings,
+        ui::style::Theme,
+    };
+
+    #[test]
+    fn test_selected_shows_reason() {
+        let mut bar = CommandBar::new(
+            Rc::new(Theme::default()),
+            Rc::new(RefCell::new(KeyConfig::default())),
+            Rc::new(Settings::default()),
+        );
+        let cmd = |name: &str| {
+            CommandInfo::new(
+                CommandText::new(name.to_string(), "", "test"),
+                true,
+                true,
+            )
+        };
+        bar.set_cmds(vec![cmd("a"), cmd("b").disabled_because("not now")]);
+        bar.refresh_width(80);
+        let shows_reason = |bar: &CommandBar| {
+            bar.cmd_infos
+                .iter()
+                .any(|c| bar.entry_text(c).contains("not now"))
+        };
+
+        assert!(!shows_reason(&bar));
+        bar.select_next(false);
+        assert!(!shows_reason(&bar));
+        bar.select_next(false);
+        assert!(shows_reason(&bar));
+        bar.select_next(false);
+        assert!(bar.selected.is_none());
+        bar.select_next(true);
+        assert!(shows_reason(&bar));
+    }
+}
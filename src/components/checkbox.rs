@@ -0,0 +1,134 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    strings,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    clicked, CommandBlocking, CommandInfo, Component, DrawableComponent,
+    EventState,
+};
+
+/// a label with a box in front, checked or not
+pub struct CheckboxComponent {
+    label: String,
+    checked: bool,
+    focused: bool,
+    area: Cell<Rect>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl CheckboxComponent {
+    pub fn new(
+        label: &str,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            label: label.to_string(),
+            checked: false,
+            focused: false,
+            area: Cell::new(Rect::default()),
+            theme,
+            key_config,
+        }
+    }
+
+    pub const fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    pub fn toggle(&mut self) {
+        self.checked = !self.checked;
+    }
+}
+
+impl DrawableComponent for CheckboxComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) -> Result<()> {
+        let mark = if self.checked { "[x]" } else { "[ ]" };
+        f.render_widget(
+            Paragraph::new(Spans::from(vec![
+                Span::styled(mark, self.theme.option(self.checked)),
+                Span::raw(" "),
+                Span::styled(
+                    self.label.as_str(),
+                    self.theme.text(true, self.focused),
+                ),
+            ])),
+            rect,
+        );
+        self.area.set(rect);
+
+        if self.focused {
+            f.set_cursor(rect.x + 1, rect.y);
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CheckboxComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.focused || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::widget_toggle(&self.key_config),
+                true,
+                true,
+            ));
+        }
+        CommandBlocking::PassingOn
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        let toggle = match ev {
+            Event::Key(key) => {
+                self.focused && key == self.key_config.borrow().text_input.toggle
+            }
+            ev => clicked(ev, self.area.get()).is_some(),
+        };
+        if toggle {
+            self.toggle();
+        }
+        Ok(toggle.into())
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.focused {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::TextInput, "toggle") => self.toggle(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self, focus: bool) {
+        self.focused = focus;
+    }
+}
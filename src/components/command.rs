@@ -1,3 +1,5 @@
+use crate::keys::ActionId;
+
 ///
 #[derive(Clone, PartialEq, PartialOrd, Ord, Eq)]
 pub struct CommandText {
@@ -9,6 +11,8 @@ pub struct CommandText {
     pub group: &'static str,
     ///
     pub hide_help: bool,
+    /// action that gets triggered when run from the command palette
+    pub action: Option<ActionId>,
 }
 
 impl CommandText {
@@ -23,9 +27,16 @@ impl CommandText {
             desc,
             group,
             hide_help: false,
+            action: None,
         }
     }
     ///
+    pub const fn action(self, action: ActionId) -> Self {
+        let mut tmp = self;
+        tmp.action = Some(action);
+        tmp
+    }
+    ///
     pub const fn hide_help(self) -> Self {
         let mut tmp = self;
         tmp.hide_help = true;
@@ -44,6 +55,8 @@ pub struct CommandInfo {
 
     /// available in current app state
     pub available: bool,
+    /// why the command is not enabled right now
+    pub reason: Option<&'static str>,
     /// used to order commands in quickbar
     pub order: i8,
 }
@@ -60,6 +73,7 @@ impl CommandInfo {
             enabled,
             quick_bar: true,
             available,
+            reason: None,
             order: 0,
         }
     }
@@ -71,6 +85,14 @@ impl CommandInfo {
         res
     }
 
+    /// disables the command and tells the user why
+    pub const fn disabled_because(self, reason: &'static str) -> Self {
+        let mut res = self;
+        res.enabled = false;
+        res.reason = Some(reason);
+        res
+    }
+
     ///
     pub const fn hidden(self) -> Self {
         let mut res = self;
@@ -0,0 +1,269 @@
+use anyhow::Result;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Clear, Paragraph},
+};
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    queue::{Action, InternalEvent, Queue},
+    strings, ui,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking, CommandInfo,
+    Component, DrawableComponent, EventState,
+};
+
+/// asks Yes or No before an `Action` runs,
+/// a confirmed action goes back to its owner through the queue
+pub struct ConfirmComponent {
+    target: Option<Action>,
+    yes: bool,
+    visible: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ConfirmComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (50, 8);
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+
+            let (title, msg) = self.get_text();
+            let style = if self.is_dangerous() {
+                self.theme.text_danger()
+            } else {
+                self.theme.text(true, false)
+            };
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::styled(msg, style),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+
+            let buttons = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)])
+                .margin(1)
+                .split(area)[1];
+            f.render_widget(
+                Paragraph::new(Spans::from(vec![
+                    Span::styled(
+                        strings::confirm_yes(&self.key_config),
+                        self.theme.text(true, self.yes),
+                    ),
+                    Span::raw("   "),
+                    Span::styled(
+                        strings::confirm_no(&self.key_config),
+                        self.theme.text(true, !self.yes),
+                    ),
+                ]))
+                .alignment(Alignment::Center),
+                buttons,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ConfirmComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::confirm_choose(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::confirm_switch(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let keys = self.key_config.borrow().clone();
+                if e == keys.popup.exit_popup {
+                    self.hide();
+                } else if e == keys.popup.select {
+                    self.choose();
+                } else if e == keys.text_input.cursor_left
+                    || e == keys.text_input.cursor_right
+                    || e == keys.text_input.focus_next
+                    || e == keys.text_input.focus_prev
+                {
+                    self.yes = !self.yes;
+                } else if e == keys.global.exit {
+                    return Ok(EventState::NotConsumed);
+                }
+
+                return Ok(EventState::Consumed);
+            }
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::Popup, "exit_popup") => self.hide(),
+            (KeyContext::Popup, "select") => self.choose(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.target = None;
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+impl ConfirmComponent {
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            target: None,
+            yes: false,
+            visible: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// asks about `action`, dangerous ones start out on No
+    pub fn open(&mut self, action: Action) -> Result<()> {
+        self.target = Some(action);
+        self.yes = !self.is_dangerous();
+        self.show()
+    }
+
+    fn choose(&mut self) {
+        if self.yes {
+            if let Some(action) = self.target.take() {
+                self.queue.push(InternalEvent::ConfirmedAction(action));
+            }
+        }
+        self.hide();
+    }
+
+    fn is_dangerous(&self) -> bool {
+        matches!(
+            self.target,
+            Some(Action::RemoveLine { .. } | Action::RemoveAllLines { .. })
+        )
+    }
+
+    fn get_text(&self) -> (String, String) {
+        match &self.target {
+            Some(Action::RemoveLine { list, index }) => (
+                strings::confirm_title_remove_line(&self.key_config),
+                strings::confirm_msg_remove_line(
+                    &self.key_config,
+                    list,
+                    *index,
+                ),
+            ),
+            Some(Action::RemoveAllLines { list }) => (
+                strings::confirm_title_remove_all(&self.key_config),
+                strings::confirm_msg_remove_all(&self.key_config, list),
+            ),
+            None => (String::new(), String::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::event::Event;
+
+    use super::ConfirmComponent;
+    use crate::{
+        components::Component,
+        keys::KeyConfig,
+        queue::{Action, InternalEvent, Queue},
+        ui::style::Theme,
+    };
+
+    #[test]
+    fn test_removal_defaults_to_no() {
+        let queue = Queue::new();
+        let key_config = Rc::new(RefCell::new(KeyConfig::default()));
+        let mut confirm = ConfirmComponent::new(
+            queue.clone(),
+            Rc::new(Theme::default()),
+            key_config.clone(),
+        );
+        let keys = key_config.borrow().clone();
+        let action = Action::RemoveAllLines {
+            list: "list1".to_string(),
+        };
+
+        confirm.open(action.clone()).unwrap();
+        confirm.event(Event::Key(keys.popup.select)).unwrap();
+        assert!(!confirm.is_visible());
+        assert!(queue.pop().is_none());
+
+        confirm.open(action.clone()).unwrap();
+        confirm
+            .event(Event::Key(keys.text_input.cursor_left))
+            .unwrap();
+        confirm.event(Event::Key(keys.popup.select)).unwrap();
+        assert!(matches!(
+            queue.pop(),
+            Some(InternalEvent::ConfirmedAction(a)) if a == action
+        ));
+    }
+}
@@ -0,0 +1,361 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    strings,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    clicked, dialog_paragraph, form::Input, input_char, CommandBlocking,
+    CommandInfo, Component, DrawableComponent, EventState,
+};
+
+/// options the open list shows at once
+const LIST_ROWS: u16 = 6;
+
+/// one of a list of options, the list opens below the field
+/// and typing narrows it down
+pub struct DropdownComponent {
+    label: String,
+    options: Vec<String>,
+    selected: usize,
+    open: bool,
+    filter: Input,
+    /// row of the open list, an index into `matches`
+    highlight: usize,
+    focused: bool,
+    area: Cell<Rect>,
+    list_area: Cell<Rect>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DropdownComponent {
+    pub fn new(
+        label: &str,
+        options: Vec<String>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            label: label.to_string(),
+            options,
+            selected: 0,
+            open: false,
+            filter: Input::default(),
+            highlight: 0,
+            focused: false,
+            area: Cell::new(Rect::default()),
+            list_area: Cell::new(Rect::default()),
+            theme,
+            key_config,
+        }
+    }
+
+    /// the selected option, `None` without options
+    pub fn selected(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+
+    /// selects the option equal to `option`, if there is one
+    pub fn select(&mut self, option: &str) {
+        if let Some(index) = self.options.iter().position(|o| o == option) {
+            self.selected = index;
+        }
+    }
+
+    pub const fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// indices of the options containing the filter, ignoring case
+    fn matches(&self) -> Vec<usize> {
+        let filter = self.filter.text().to_lowercase();
+        self.options
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.to_lowercase().contains(&filter))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn open(&mut self) {
+        self.open = true;
+        self.filter.clear();
+        self.highlight = self.selected;
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.filter.clear();
+    }
+
+    fn pick(&mut self, row: usize) {
+        if let Some(index) = self.matches().get(row) {
+            self.selected = *index;
+        }
+        self.close();
+    }
+
+    /// first row of the open list that is visible
+    fn scroll(&self) -> usize {
+        self.highlight
+            .saturating_sub(usize::from(LIST_ROWS).saturating_sub(1))
+    }
+
+    fn open_event(&mut self, key: KeyEvent) {
+        let keys = self.key_config.borrow().text_input.clone();
+        let count = self.matches().len();
+        if key == keys.cancel {
+            self.close();
+        } else if key == keys.confirm {
+            self.pick(self.highlight);
+        } else if key == self.key_config.borrow().popup.scroll_up {
+            self.highlight = self.highlight.saturating_sub(1);
+        } else if key == self.key_config.borrow().popup.scroll_down {
+            self.highlight = (self.highlight + 1).min(count.saturating_sub(1));
+        } else if self.filter.event(key, &keys) {
+            self.highlight = 0;
+        }
+    }
+
+    fn closed_event(&mut self, key: KeyEvent) -> bool {
+        let keys = self.key_config.borrow().text_input.clone();
+        if self.options.is_empty() {
+            return false;
+        }
+        if key == keys.toggle {
+            self.open();
+        } else if key == keys.cursor_left {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.options.len() - 1);
+        } else if key == keys.cursor_right {
+            self.selected = (self.selected + 1) % self.options.len();
+        } else if let Some(c) = input_char(key) {
+            self.open();
+            self.filter.insert(c);
+            self.highlight = 0;
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn draw_list<B: Backend>(&self, f: &mut Frame<B>, below: Rect) {
+        let matches = self.matches();
+        let rows = (matches.len() as u16).clamp(1, LIST_ROWS);
+        let bottom = f.size().bottom();
+        let area = Rect::new(
+            below.x,
+            below.bottom().min(bottom),
+            below.width,
+            (rows + 2).min(bottom.saturating_sub(below.bottom())),
+        );
+
+        let lines: Vec<Spans> = if matches.is_empty() {
+            vec![Spans::from(Span::styled(
+                strings::dropdown_no_match(&self.key_config),
+                self.theme.text(false, false),
+            ))]
+        } else {
+            matches
+                .iter()
+                .enumerate()
+                .skip(self.scroll())
+                .take(usize::from(rows))
+                .map(|(row, index)| {
+                    Spans::from(Span::styled(
+                        self.options[*index].as_str(),
+                        self.theme.text(true, row == self.highlight),
+                    ))
+                })
+                .collect()
+        };
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.theme.block(true)),
+            ),
+            area,
+        );
+        self.list_area.set(area);
+    }
+}
+
+impl DrawableComponent for DropdownComponent {
+    /// the field takes three rows of `rect`,
+    /// an open list is drawn below them on top of whatever is there
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) -> Result<()> {
+        let area = Rect { height: rect.height.min(3), ..rect };
+        let content = if self.open {
+            Text::raw(self.filter.text())
+        } else {
+            Text::styled(
+                format!("{} ▾", self.selected().unwrap_or_default()),
+                self.theme.text(true, false),
+            )
+        };
+        f.render_widget(
+            dialog_paragraph(&self.label, content, &self.theme, self.focused),
+            area,
+        );
+        self.area.set(area);
+
+        if self.open {
+            self.draw_list(f, area);
+            if self.focused {
+                let (_, column) = self.filter.cursor();
+                f.set_cursor(
+                    (area.x + 1 + column).min(area.right().saturating_sub(2)),
+                    area.y + 1,
+                );
+            }
+        } else {
+            self.list_area.set(Rect::default());
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for DropdownComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.focused || force_all {
+            if self.open {
+                out.push(CommandInfo::new(
+                    strings::commands::dropdown_pick(&self.key_config),
+                    !self.matches().is_empty(),
+                    true,
+                ));
+            } else {
+                out.push(CommandInfo::new(
+                    strings::commands::dropdown_open(&self.key_config),
+                    !self.options.is_empty(),
+                    true,
+                ));
+                out.push(CommandInfo::new(
+                    strings::commands::form_select(&self.key_config),
+                    self.options.len() > 1,
+                    true,
+                ));
+            }
+        }
+        CommandBlocking::PassingOn
+    }
+
+    /// an open list takes every key but exit
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if let Event::Key(key) = ev {
+            if !self.focused || key == self.key_config.borrow().global.exit {
+                return Ok(EventState::NotConsumed);
+            }
+            if self.open {
+                self.open_event(key);
+                return Ok(EventState::Consumed);
+            }
+            return Ok(self.closed_event(key).into());
+        }
+
+        let list = self.list_area.get();
+        if let Some((_, row)) = clicked(ev, list) {
+            if row > list.y && row + 1 < list.bottom() {
+                self.pick(self.scroll() + usize::from(row - list.y - 1));
+            }
+            return Ok(EventState::Consumed);
+        }
+        if clicked(ev, self.area.get()).is_some() {
+            if self.open {
+                self.close();
+            } else if !self.options.is_empty() {
+                self.open();
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.focused || self.open || self.options.is_empty() {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::TextInput, "toggle") => self.open(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// losing focus closes the list
+    fn focus(&mut self, focus: bool) {
+        self.focused = focus;
+        if !focus {
+            self.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::DropdownComponent;
+    use crate::{components::Component, keys::KeyConfig, ui::style::Theme};
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn test_filter_and_pick() {
+        let mut dropdown = DropdownComponent::new(
+            "Fruit",
+            vec!["Apple".into(), "Banana".into(), "Cherry".into()],
+            Rc::new(Theme::default()),
+            Rc::new(RefCell::new(KeyConfig::default())),
+        );
+        assert!(!dropdown.event(key(KeyCode::Char('a'))).unwrap().is_consumed());
+
+        dropdown.focus(true);
+        dropdown.event(key(KeyCode::Char('A'))).unwrap();
+        assert!(dropdown.is_open());
+        assert_eq!(dropdown.matches(), vec![0, 1]);
+
+        dropdown.event(key(KeyCode::Down)).unwrap();
+        dropdown.event(key(KeyCode::Enter)).unwrap();
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.selected(), Some("Banana"));
+
+        dropdown.event(key(KeyCode::Char(' '))).unwrap();
+        dropdown.event(key(KeyCode::Char('x'))).unwrap();
+        assert!(dropdown.matches().is_empty());
+        dropdown.event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(dropdown.selected(), Some("Banana"));
+    }
+}
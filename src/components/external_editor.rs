@@ -0,0 +1,116 @@
+use std::{env, fs, io::Write, process::Command};
+
+use anyhow::{anyhow, bail, Result};
+use tempfile::TempPath;
+
+/// variables naming the editor, the first one set wins
+const EDITOR_VARS: [&str; 3] = ["GIT_EDITOR", "VISUAL", "EDITOR"];
+
+/// the editor command and its arguments from the first variable
+/// of `EDITOR_VARS` that `var` finds set
+fn editor_command(
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<(String, Vec<String>)> {
+    let editor = EDITOR_VARS
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.trim().is_empty())
+        .ok_or_else(|| {
+            anyhow!(
+                "no editor configured, set one of ${}",
+                EDITOR_VARS.join(", $")
+            )
+        })?;
+
+    // arguments containing whitespace are not supported
+    let mut parts = editor.split_whitespace().map(String::from);
+    let command = parts.next().unwrap_or_default();
+    Ok((command, parts.collect()))
+}
+
+/// a new file holding `text` that only the user may read,
+/// created exclusively under a random name and removed once dropped
+fn temp_file(text: &str) -> Result<TempPath> {
+    let mut file = tempfile::Builder::new()
+        .prefix("tuisample-edit-")
+        .suffix(".txt")
+        .tempfile()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+    Ok(file.into_temp_path())
+}
+
+/// runs the user's editor on the whole terminal
+pub struct ExternalEditor;
+
+impl ExternalEditor {
+    /// lets the editor change `text` in a temporary file and returns
+    /// what it saved, the caller hands it the terminal and input first
+    pub fn edit(text: &str) -> Result<String> {
+        let (command, args) = editor_command(|name| env::var(name).ok())?;
+
+        // removed when it goes out of scope, errors and panics included
+        let file = temp_file(text)?;
+
+        log::info!("opening editor '{}'", command);
+        let status = Command::new(&command)
+            .args(&args)
+            .arg(&file)
+            .status()
+            .map_err(|e| anyhow!("failed to run editor '{}': {}", command, e))?;
+        if !status.success() {
+            bail!("editor '{}' failed: {}", command, status);
+        }
+
+        Ok(fs::read_to_string(&file)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{editor_command, temp_file};
+
+    #[test]
+    fn test_editor_command() {
+        let vars = |set: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                set.iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| (*v).to_string())
+            }
+        };
+
+        assert_eq!(
+            editor_command(vars(&[("EDITOR", "vi"), ("GIT_EDITOR", "code --wait")]))
+                .unwrap(),
+            ("code".to_string(), vec!["--wait".to_string()])
+        );
+        assert_eq!(
+            editor_command(vars(&[("VISUAL", " "), ("EDITOR", "nano")]))
+                .unwrap()
+                .0,
+            "nano"
+        );
+        assert!(editor_command(vars(&[])).is_err());
+    }
+
+    #[test]
+    fn test_temp_file() {
+        let file = temp_file("draft").unwrap();
+        let path = file.to_path_buf();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "draft");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_ne!(temp_file("draft").unwrap().to_path_buf(), path);
+
+        drop(file);
+        assert!(!path.exists());
+    }
+}
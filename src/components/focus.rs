@@ -0,0 +1,120 @@
+/// cycles focus through a fixed set of elements and
+/// remembers earlier focus so it can be handed back
+pub struct FocusRing<T> {
+    elements: Vec<T>,
+    current: usize,
+    history: Vec<usize>,
+}
+
+impl<T: Clone + PartialEq> FocusRing<T> {
+    /// the first element starts focused
+    pub fn new(elements: Vec<T>) -> Self {
+        Self {
+            elements,
+            current: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// `None` if the ring has no elements
+    pub fn current(&self) -> Option<&T> {
+        self.elements.get(self.current)
+    }
+
+    pub fn is(&self, element: &T) -> bool {
+        self.current() == Some(element)
+    }
+
+    pub fn contains(&self, element: &T) -> bool {
+        self.elements.contains(element)
+    }
+
+    /// the element `next` or `prev` would focus
+    pub fn peek(&self, forward: bool) -> Option<&T> {
+        let len = self.elements.len();
+        if len == 0 {
+            return None;
+        }
+        let index = if forward {
+            (self.current + 1) % len
+        } else {
+            self.current.checked_sub(1).unwrap_or(len - 1)
+        };
+        self.elements.get(index)
+    }
+
+    pub fn next(&mut self) {
+        if !self.elements.is_empty() {
+            self.current = (self.current + 1) % self.elements.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.elements.is_empty() {
+            self.current = self
+                .current
+                .checked_sub(1)
+                .unwrap_or(self.elements.len() - 1);
+        }
+    }
+
+    /// focuses `element`, returns `false` if it is not part of the ring
+    pub fn set(&mut self, element: &T) -> bool {
+        match self.elements.iter().position(|e| e == element) {
+            Some(index) => {
+                self.current = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// focuses `element` and remembers the current focus for `back`
+    pub fn push(&mut self, element: &T) -> bool {
+        let previous = self.current;
+        let found = self.set(element);
+        if found {
+            self.history.push(previous);
+        }
+        found
+    }
+
+    /// returns focus to where it was before the last `push`
+    pub fn back(&mut self) {
+        if let Some(previous) = self.history.pop() {
+            self.current = previous;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FocusRing;
+
+    #[test]
+    fn test_cycle_and_history() {
+        let mut ring = FocusRing::new(vec!["a", "b", "c"]);
+
+        assert_eq!(ring.peek(false), Some(&"c"));
+        ring.prev();
+        assert!(ring.is(&"c"));
+        assert_eq!(ring.peek(true), Some(&"a"));
+        ring.next();
+        assert!(ring.is(&"a"));
+
+        assert!(ring.push(&"b"));
+        assert!(ring.push(&"c"));
+        assert!(!ring.push(&"d"));
+        ring.back();
+        assert!(ring.is(&"b"));
+        ring.back();
+        assert!(ring.is(&"a"));
+        ring.back();
+        assert!(ring.is(&"a"));
+    }
+}
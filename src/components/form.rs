@@ -0,0 +1,1097 @@
+use std::cell::RefCell;
+
+use anyhow::{anyhow, Result};
+use crossterm::event::{Event, KeyEvent};
+use tui::{
+    backend::Backend,
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    auth::Secret,
+    keys::{ActionId, KeyContext, SharedKeyConfig, TextInputKeys},
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+
+use super::{
+    clicked, input_char, visibility_blocking, CheckboxComponent,
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    DropdownComponent, EventState, FocusRing,
+    RadioGroupComponent, SpinBoxComponent,
+};
+
+/// ticks a revealed password stays readable
+const REVEAL_TICKS: u16 = 25;
+
+/// a single line of editable text, the cursor is a byte position
+#[derive(Default)]
+pub struct Input {
+    text: String,
+    position: usize,
+}
+
+impl Input {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.position = self.text.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.position = 0;
+    }
+
+    /// the text as drawn, `mask` covers every column a character takes
+    pub fn shown(&self, mask: Option<char>) -> String {
+        match mask {
+            Some(mask) => self
+                .text
+                .chars()
+                .map(|c| mask.to_string().repeat(c.width().unwrap_or(1)))
+                .collect(),
+            None => self.text.clone(),
+        }
+    }
+
+    /// line and display column of the cursor
+    pub fn cursor(&self) -> (u16, u16) {
+        let before = &self.text[..self.position];
+        let line = before.matches('\n').count();
+        let column = before.rsplit('\n').next().unwrap_or_default().width();
+        (line as u16, column as u16)
+    }
+
+    fn cursor_left(&mut self) -> bool {
+        match self.text[..self.position].chars().next_back() {
+            Some(c) => {
+                self.position -= c.len_utf8();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cursor_right(&mut self) {
+        if let Some(c) = self.text[self.position..].chars().next() {
+            self.position += c.len_utf8();
+        }
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.position, c);
+        self.position += c.len_utf8();
+    }
+
+    /// applies an editing key, `false` if it is none
+    pub fn event(&mut self, key: KeyEvent, keys: &TextInputKeys) -> bool {
+        if key == keys.delete_back {
+            if self.cursor_left() {
+                self.text.remove(self.position);
+            }
+        } else if key == keys.delete_forward {
+            if self.position < self.text.len() {
+                self.text.remove(self.position);
+            }
+        } else if key == keys.cursor_left {
+            self.cursor_left();
+        } else if key == keys.cursor_right {
+            self.cursor_right();
+        } else if let Some(c) = input_char(key) {
+            self.insert(c);
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+/// what a field edits
+pub enum FieldKind {
+    Text,
+    /// masked unless revealed, the value is a `Secret`
+    Password,
+    /// whole numbers in `min..=max`, stepped by `step`
+    Number { min: i64, max: i64, step: i64 },
+    Checkbox,
+    /// one of the options, all of them side by side
+    Radio(Vec<String>),
+    /// one of the options, picked from a list that filters while typing
+    Select(Vec<String>),
+    /// text with line breaks, `rows` lines are visible
+    MultiLine { rows: u16 },
+}
+
+/// the value of a field, handed to validators and to the caller
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Text(String),
+    Secret(Secret),
+    /// `None` while the field is empty
+    Number(Option<i64>),
+    Checked(bool),
+    Choice(String),
+}
+
+impl FieldValue {
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(text) | Self::Choice(text) => text.is_empty(),
+            Self::Secret(secret) => secret.expose().is_empty(),
+            Self::Number(number) => number.is_none(),
+            Self::Checked(checked) => !checked,
+        }
+    }
+}
+
+/// checks a value, returns the message to show below the field
+type Check = Box<dyn Fn(&FieldValue) -> Option<String>>;
+
+/// the component editing a field that is no plain text
+enum Widget {
+    Checkbox(CheckboxComponent),
+    Radio(RadioGroupComponent),
+    Dropdown(DropdownComponent),
+    SpinBox(SpinBoxComponent),
+}
+
+impl Widget {
+    fn component(&self) -> &dyn Component {
+        match self {
+            Self::Checkbox(c) => c,
+            Self::Radio(c) => c,
+            Self::Dropdown(c) => c,
+            Self::SpinBox(c) => c,
+        }
+    }
+
+    fn component_mut(&mut self) -> &mut dyn Component {
+        match self {
+            Self::Checkbox(c) => c,
+            Self::Radio(c) => c,
+            Self::Dropdown(c) => c,
+            Self::SpinBox(c) => c,
+        }
+    }
+
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) -> Result<()> {
+        match self {
+            Self::Checkbox(c) => c.draw(f, rect),
+            Self::Radio(c) => c.draw(f, rect),
+            Self::Dropdown(c) => c.draw(f, rect),
+            Self::SpinBox(c) => c.draw(f, rect),
+        }
+    }
+
+    fn value(&self) -> FieldValue {
+        match self {
+            Self::Checkbox(c) => FieldValue::Checked(c.is_checked()),
+            Self::Radio(c) => {
+                FieldValue::Choice(c.selected().unwrap_or_default().to_string())
+            }
+            Self::Dropdown(c) => {
+                FieldValue::Choice(c.selected().unwrap_or_default().to_string())
+            }
+            Self::SpinBox(c) => FieldValue::Number(c.value()),
+        }
+    }
+}
+
+/// one entry of a form schema, built like
+/// `FormField::text("name", "Name").required()`
+pub struct FormField {
+    name: &'static str,
+    label: String,
+    kind: FieldKind,
+    required: bool,
+    validators: Vec<Check>,
+    hints: Vec<Check>,
+    input: Input,
+    checked: bool,
+    /// created once the field is added to a form
+    widget: Option<Widget>,
+    error: Option<String>,
+}
+
+impl FormField {
+    fn new(name: &'static str, label: &str, kind: FieldKind) -> Self {
+        Self {
+            name,
+            label: label.to_string(),
+            kind,
+            required: false,
+            validators: Vec::new(),
+            hints: Vec::new(),
+            input: Input::default(),
+            checked: false,
+            widget: None,
+            error: None,
+        }
+    }
+
+    pub fn text(name: &'static str, label: &str) -> Self {
+        Self::new(name, label, FieldKind::Text)
+    }
+
+    pub fn password(name: &'static str, label: &str) -> Self {
+        Self::new(name, label, FieldKind::Password)
+    }
+
+    pub fn number(name: &'static str, label: &str, min: i64, max: i64) -> Self {
+        Self::new(name, label, FieldKind::Number { min, max, step: 1 })
+    }
+
+    pub fn checkbox(name: &'static str, label: &str) -> Self {
+        Self::new(name, label, FieldKind::Checkbox)
+    }
+
+    pub fn radio(name: &'static str, label: &str, options: Vec<String>) -> Self {
+        Self::new(name, label, FieldKind::Radio(options))
+    }
+
+    pub fn select(name: &'static str, label: &str, options: Vec<String>) -> Self {
+        Self::new(name, label, FieldKind::Select(options))
+    }
+
+    pub fn multi_line(name: &'static str, label: &str, rows: u16) -> Self {
+        Self::new(name, label, FieldKind::MultiLine { rows })
+    }
+
+    /// empty values do not pass, a checkbox has to be checked
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// `check` runs on submit, a message keeps the form from submitting
+    pub fn validate(
+        mut self,
+        check: impl Fn(&FieldValue) -> Option<String> + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(check));
+        self
+    }
+
+    /// `check` runs while drawing, its message is only a hint
+    pub fn hint(
+        mut self,
+        check: impl Fn(&FieldValue) -> Option<String> + 'static,
+    ) -> Self {
+        self.hints.push(Box::new(check));
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// what the arrow keys add to or take from a number
+    pub fn step(mut self, step: i64) -> Self {
+        if let FieldKind::Number { step: s, .. } = &mut self.kind {
+            *s = step;
+        }
+        self
+    }
+
+    fn title(&self) -> String {
+        if self.required {
+            format!("{} *", self.label)
+        } else {
+            self.label.clone()
+        }
+    }
+
+    /// creates the widget for kinds that are no plain text
+    fn attach(&mut self, theme: &SharedTheme, key_config: &SharedKeyConfig) {
+        let title = self.title();
+        let (theme, key_config) = (theme.clone(), key_config.clone());
+        self.widget = match &self.kind {
+            FieldKind::Checkbox => {
+                let mut checkbox =
+                    CheckboxComponent::new(&title, theme, key_config);
+                checkbox.set_checked(self.checked);
+                Some(Widget::Checkbox(checkbox))
+            }
+            FieldKind::Radio(options) => Some(Widget::Radio(
+                RadioGroupComponent::new(&title, options.clone(), theme, key_config),
+            )),
+            FieldKind::Select(options) => Some(Widget::Dropdown(
+                DropdownComponent::new(&title, options.clone(), theme, key_config),
+            )),
+            FieldKind::Number { min, max, step } => Some(Widget::SpinBox(
+                SpinBoxComponent::new(&title, *min, *max, theme, key_config)
+                    .step(*step),
+            )),
+            _ => None,
+        };
+    }
+
+    /// plain text the external editor may see, passwords stay out of files
+    fn is_editable(&self) -> bool {
+        matches!(self.kind, FieldKind::Text | FieldKind::MultiLine { .. })
+    }
+
+    fn value(&self) -> FieldValue {
+        match (&self.widget, &self.kind) {
+            (Some(widget), _) => widget.value(),
+            (None, FieldKind::Password) => {
+                FieldValue::Secret(Secret::new(self.input.text.clone()))
+            }
+            (None, _) => FieldValue::Text(self.input.text.clone()),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.input.clear();
+        self.error = None;
+        match &mut self.widget {
+            Some(Widget::SpinBox(spin_box)) => spin_box.set_value(None),
+            Some(widget) => widget.component_mut().focus(false),
+            None => (),
+        }
+    }
+
+    /// rows the field takes, its message line included
+    fn height(&self) -> u16 {
+        match self.kind {
+            FieldKind::Checkbox => 2,
+            FieldKind::MultiLine { rows } => rows + 3,
+            _ => 4,
+        }
+    }
+
+    fn check(&self, key_config: &SharedKeyConfig) -> Option<String> {
+        let value = self.value();
+        if self.required && value.is_empty() {
+            return Some(strings::form_required(key_config, &self.label));
+        }
+        if let (FieldKind::Number { min, max, .. }, Some(Widget::SpinBox(spin_box))) =
+            (&self.kind, &self.widget)
+        {
+            let (min, max) = (*min, *max);
+            match value {
+                FieldValue::Number(None) if !spin_box.text().is_empty() => {
+                    return Some(strings::form_not_a_number(key_config));
+                }
+                FieldValue::Number(Some(n)) if n < min || n > max => {
+                    return Some(strings::form_out_of_range(
+                        key_config, min, max,
+                    ));
+                }
+                _ => (),
+            }
+        }
+        self.validators.iter().find_map(|check| check(&value))
+    }
+
+    /// applies a key to the field, `false` if it does not take it
+    fn event(&mut self, key: KeyEvent, keys: &TextInputKeys) -> Result<bool> {
+        let taken = match (&mut self.widget, &self.kind) {
+            (Some(widget), _) => {
+                widget.component_mut().event(Event::Key(key))?.is_consumed()
+            }
+            (None, FieldKind::MultiLine { .. }) if key == keys.confirm => {
+                self.input.insert('\n');
+                true
+            }
+            (None, _) => self.input.event(key, keys),
+        };
+        if taken {
+            self.error = None;
+        }
+        Ok(taken)
+    }
+}
+
+/// the submitted values in schema order
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormValues {
+    values: Vec<(&'static str, FieldValue)>,
+}
+
+impl FormValues {
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.values.iter().find(|(n, _)| *n == name).map(|(_, v)| v)
+    }
+
+    /// text of a text, multi line or select field
+    pub fn text(&self, name: &str) -> Result<String> {
+        match self.get(name) {
+            Some(FieldValue::Text(text)) | Some(FieldValue::Choice(text)) => {
+                Ok(text.clone())
+            }
+            _ => Err(anyhow!("form has no text field '{}'", name)),
+        }
+    }
+
+    pub fn secret(&self, name: &str) -> Result<Secret> {
+        match self.get(name) {
+            Some(FieldValue::Secret(secret)) => Ok(secret.clone()),
+            _ => Err(anyhow!("form has no password field '{}'", name)),
+        }
+    }
+
+    pub fn number(&self, name: &str) -> Result<Option<i64>> {
+        match self.get(name) {
+            Some(FieldValue::Number(number)) => Ok(*number),
+            _ => Err(anyhow!("form has no number field '{}'", name)),
+        }
+    }
+
+    pub fn checked(&self, name: &str) -> Result<bool> {
+        match self.get(name) {
+            Some(FieldValue::Checked(checked)) => Ok(*checked),
+            _ => Err(anyhow!("form has no checkbox '{}'", name)),
+        }
+    }
+}
+
+/// what a caller turns the submitted values into
+pub trait FromForm: Sized {
+    fn from_form(values: &FormValues) -> Result<Self>;
+}
+
+/// a dialog built from a list of fields, validates them on submit
+/// and keeps the values until the caller takes them
+pub struct FormComponent {
+    title: String,
+    submit_label: String,
+    width: u16,
+    fields: Vec<FormField>,
+    /// fields by index, the submit button comes last
+    focus: FocusRing<usize>,
+    visible: bool,
+    enabled: bool,
+    revealed: u16,
+    status: Option<(String, Style)>,
+    submitted: Option<FormValues>,
+    /// the field waiting for the external editor
+    editing: Option<usize>,
+    /// where fields and button got drawn, for mouse clicks
+    areas: RefCell<Vec<Rect>>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl FormComponent {
+    pub fn new(
+        title: &str,
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            title: title.to_string(),
+            submit_label: strings::form_submit(&key_config),
+            width: 44,
+            fields: Vec::new(),
+            focus: FocusRing::new(vec![0]),
+            visible: false,
+            enabled: true,
+            revealed: 0,
+            status: None,
+            submitted: None,
+            editing: None,
+            areas: RefCell::new(Vec::new()),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    pub fn field(mut self, mut field: FormField) -> Self {
+        field.attach(&self.theme, &self.key_config);
+        self.fields.push(field);
+        self.focus = FocusRing::new((0..=self.fields.len()).collect());
+        self.sync_focus();
+        self
+    }
+
+    pub fn submit_label(mut self, label: &str) -> Self {
+        self.submit_label = label.to_string();
+        self
+    }
+
+    pub fn width(mut self, width: u16) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// the values of the last submit, converted for the caller
+    pub fn take_submitted<T: FromForm>(&mut self) -> Option<Result<T>> {
+        self.submitted.take().map(|values| T::from_form(&values))
+    }
+
+    /// a line below the button, e.g. the outcome of the submit
+    pub fn set_status(&mut self, status: Option<(String, Style)>) {
+        self.status = status;
+    }
+
+    /// a disabled form shows no cursor and ignores input
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.sync_focus();
+    }
+
+    pub fn set_text(&mut self, name: &str, text: &str) {
+        if let Some(field) = self.field_mut(name) {
+            field.input.set_text(text);
+        }
+    }
+
+    /// selects `option` of a radio or select field
+    pub fn set_choice(&mut self, name: &str, option: &str) {
+        match self.field_mut(name).and_then(|f| f.widget.as_mut()) {
+            Some(Widget::Radio(radio)) => radio.select(option),
+            Some(Widget::Dropdown(dropdown)) => dropdown.select(option),
+            _ => (),
+        }
+    }
+
+    pub fn focus_field(&mut self, name: &str) {
+        if let Some(index) = self.fields.iter().position(|f| f.name == name) {
+            self.focus.set(&index);
+            self.sync_focus();
+        }
+    }
+
+    pub fn value(&self, name: &str) -> Option<FieldValue> {
+        self.fields.iter().find(|f| f.name == name).map(FormField::value)
+    }
+
+    /// empties all text, keeps checkboxes and selections
+    pub fn clear(&mut self) {
+        for field in &mut self.fields {
+            field.clear();
+        }
+        self.revealed = 0;
+        self.focus.reset();
+        self.sync_focus();
+    }
+
+    /// masks a revealed password again in time
+    pub fn on_tick(&mut self) {
+        self.revealed = self.revealed.saturating_sub(1);
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut FormField> {
+        self.fields.iter_mut().find(|f| f.name == name)
+    }
+
+    fn focused_field(&self) -> Option<&FormField> {
+        self.focus.current().and_then(|i| self.fields.get(*i))
+    }
+
+    fn focused_widget(&mut self) -> Option<&mut Widget> {
+        let index = *self.focus.current()?;
+        self.fields.get_mut(index)?.widget.as_mut()
+    }
+
+    /// only the widget of the focused field takes keys and shows a cursor
+    fn sync_focus(&mut self) {
+        let focused = self.focus.current().copied();
+        for (i, field) in self.fields.iter_mut().enumerate() {
+            if let Some(widget) = &mut field.widget {
+                widget
+                    .component_mut()
+                    .focus(self.enabled && focused == Some(i));
+            }
+        }
+    }
+
+    /// lets the external editor change the focused text field
+    fn open_editor(&mut self) {
+        let index = match self.focus.current().copied() {
+            Some(i)
+                if self.fields.get(i).is_some_and(FormField::is_editable) =>
+            {
+                i
+            }
+            _ => return,
+        };
+        self.queue.push(InternalEvent::OpenExternalEditor(
+            self.fields[index].input.text().to_string(),
+        ));
+        self.editing = Some(index);
+    }
+
+    fn toggle_revealed(&mut self) {
+        self.revealed = if self.revealed > 0 { 0 } else { REVEAL_TICKS };
+    }
+
+    fn has_password(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.kind, FieldKind::Password))
+    }
+
+    /// validates every field, the values wait for `take_submitted`
+    fn submit(&mut self) {
+        self.revealed = 0;
+        for field in &mut self.fields {
+            field.error = field.check(&self.key_config);
+        }
+        match self.fields.iter().position(|f| f.error.is_some()) {
+            Some(invalid) => {
+                self.focus.set(&invalid);
+            }
+            None => {
+                self.submitted = Some(FormValues {
+                    values: self
+                        .fields
+                        .iter()
+                        .map(|f| (f.name, f.value()))
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    fn size(&self) -> (u16, u16) {
+        let fields: u16 = self.fields.iter().map(FormField::height).sum();
+        // border, button and status line
+        (self.width, fields + 4)
+    }
+
+    fn draw_field<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        field: &FormField,
+        focused: bool,
+        r: Rect,
+    ) -> Result<()> {
+        let style = if focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        let label = field.title();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [Constraint::Length(r.height.saturating_sub(1)), Constraint::Length(1)]
+                    .as_ref(),
+            )
+            .split(r);
+
+        let block = Block::default().borders(Borders::ALL).title(label.clone());
+        let mut cursor = None;
+        match (&field.widget, &field.kind) {
+            (Some(widget), _) => widget.draw(f, chunks[0])?,
+            (None, FieldKind::MultiLine { rows }) => {
+                let (line, column) = field.input.cursor();
+                let scroll = line.saturating_sub(rows.saturating_sub(1));
+                f.render_widget(
+                    Paragraph::new(field.input.text.as_str())
+                        .style(style)
+                        .block(block)
+                        .scroll((scroll, 0)),
+                    chunks[0],
+                );
+                cursor = Some((
+                    chunks[0].x + 1 + column,
+                    chunks[0].y + 1 + line - scroll,
+                ));
+            }
+            (None, kind) => {
+                let mask = match kind {
+                    FieldKind::Password if self.revealed == 0 => Some('*'),
+                    _ => None,
+                };
+                let title = match kind {
+                    FieldKind::Password if self.revealed > 0 => {
+                        strings::form_revealed(&self.key_config, &label)
+                    }
+                    _ => label,
+                };
+                f.render_widget(
+                    Paragraph::new(field.input.shown(mask))
+                        .style(style)
+                        .block(block.title(title)),
+                    chunks[0],
+                );
+                let (_, column) = field.input.cursor();
+                cursor = Some((chunks[0].x + 1 + column, chunks[0].y + 1));
+            }
+        }
+
+        let value = field.value();
+        let message = field
+            .error
+            .clone()
+            .map(|e| (e, Color::Red))
+            .or_else(|| {
+                field
+                    .hints
+                    .iter()
+                    .find_map(|hint| hint(&value))
+                    .map(|h| (h, Color::Yellow))
+            });
+        if let Some((msg, color)) = message {
+            f.render_widget(
+                Paragraph::new(msg).style(Style::default().fg(color)),
+                chunks[1],
+            );
+        }
+
+        if let (true, true, Some((x, y))) = (focused, self.enabled, cursor) {
+            f.set_cursor(x.min(chunks[0].right().saturating_sub(2)), y);
+        }
+
+        Ok(())
+    }
+}
+
+impl DrawableComponent for FormComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, _rect: Rect) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let (width, height) = self.size();
+        let area = ui::centered_rect_absolute(width, height, f.size());
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().title(self.title.as_str()).borders(Borders::ALL),
+            area,
+        );
+
+        let mut constraints: Vec<Constraint> = self
+            .fields
+            .iter()
+            .map(|field| Constraint::Length(field.height()))
+            .collect();
+        constraints.extend([Constraint::Length(1), Constraint::Length(1)]);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(constraints)
+            .split(area);
+
+        for (i, field) in self.fields.iter().enumerate() {
+            self.draw_field(f, field, self.focus.is(&i), chunks[i])?;
+        }
+        // an open dropdown lists its options on top of the fields below
+        if let Some(i) = self.focus.current().copied() {
+            if let Some(Some(Widget::Dropdown(dropdown))) =
+                self.fields.get(i).map(|f| &f.widget)
+            {
+                if dropdown.is_open() {
+                    dropdown.draw(f, chunks[i])?;
+                }
+            }
+        }
+
+        let button = chunks[self.fields.len()];
+        let button_style = if self.focus.is(&self.fields.len()) {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        f.render_widget(
+            Paragraph::new(format!("[ {} ]", self.submit_label))
+                .style(button_style)
+                .alignment(Alignment::Center),
+            button,
+        );
+        if let Some((status, style)) = &self.status {
+            f.render_widget(
+                Paragraph::new(status.as_str())
+                    .style(*style)
+                    .alignment(Alignment::Center),
+                chunks[self.fields.len() + 1],
+            );
+        }
+
+        *self.areas.borrow_mut() =
+            chunks.iter().take(self.fields.len() + 1).copied().collect();
+
+        Ok(())
+    }
+}
+
+impl Component for FormComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::input_confirm(&self.key_config),
+                self.enabled,
+                self.visible,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_switch_field(&self.key_config),
+                true,
+                self.visible,
+            ));
+            if self.has_password() {
+                out.push(CommandInfo::new(
+                    strings::commands::input_reveal_password(&self.key_config),
+                    true,
+                    self.visible,
+                ));
+            }
+            if let Some(widget) =
+                self.focused_field().and_then(|f| f.widget.as_ref())
+            {
+                widget.component().commands(out, force_all);
+            }
+            if self.focused_field().is_some_and(FormField::is_editable) {
+                out.push(CommandInfo::new(
+                    strings::commands::input_open_editor(&self.key_config),
+                    self.enabled,
+                    self.visible,
+                ));
+            }
+            out.push(CommandInfo::new(
+                strings::commands::input_cancel(&self.key_config),
+                true,
+                self.visible,
+            ));
+        }
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+
+        if let Event::Key(key) = ev {
+            let keys = self.key_config.borrow().text_input.clone();
+            if key == self.key_config.borrow().global.exit {
+                return Ok(EventState::NotConsumed);
+            }
+            if !self.enabled {
+                return Ok(EventState::Consumed);
+            }
+
+            let taken = match self.focus.current().copied() {
+                Some(i) if i < self.fields.len() => {
+                    self.fields[i].event(key, &keys)?
+                }
+                _ => false,
+            };
+            if taken {
+                return Ok(EventState::Consumed);
+            }
+
+            if key == keys.confirm {
+                self.submit();
+            } else if key == keys.next_field || key == keys.focus_next {
+                self.focus.next();
+            } else if key == keys.prev_field || key == keys.focus_prev {
+                self.focus.prev();
+            } else if key == keys.open_editor {
+                self.open_editor();
+            } else if key == keys.reveal_password && self.has_password() {
+                self.toggle_revealed();
+            } else if key == keys.cancel {
+                self.hide();
+            }
+            self.sync_focus();
+            // nothing behind the form gets keys while it is open
+            return Ok(EventState::Consumed);
+        }
+
+        if let Event::Mouse(_) = ev {
+            if !self.enabled {
+                return Ok(EventState::Consumed);
+            }
+            // an open dropdown list covers the fields below it
+            if let Some(widget) = self.focused_widget() {
+                if widget.component_mut().event(ev)?.is_consumed() {
+                    return Ok(EventState::Consumed);
+                }
+            }
+            let hit = self
+                .areas
+                .borrow()
+                .iter()
+                .position(|r| clicked(ev, *r).is_some());
+            if let Some(i) = hit {
+                self.focus.set(&i);
+                self.sync_focus();
+                match self.fields.get_mut(i) {
+                    Some(field) => {
+                        if let Some(widget) = &mut field.widget {
+                            widget.component_mut().event(ev)?;
+                        }
+                    }
+                    None => self.submit(),
+                }
+            }
+            self.sync_focus();
+            return Ok(EventState::Consumed);
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible || !self.enabled {
+            return Ok(EventState::NotConsumed);
+        }
+        if let Some(widget) = self.focused_widget() {
+            if widget.component_mut().run_action(action)?.is_consumed() {
+                return Ok(EventState::Consumed);
+            }
+        }
+        match (action.context, action.action) {
+            (KeyContext::TextInput, "confirm") => self.submit(),
+            (KeyContext::TextInput, "open_editor") => self.open_editor(),
+            (KeyContext::TextInput, "reveal_password")
+                if self.has_password() =>
+            {
+                self.toggle_revealed();
+            }
+            (KeyContext::TextInput, "cancel") => self.hide(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        self.sync_focus();
+        Ok(EventState::Consumed)
+    }
+
+    /// a single line field keeps the first line only
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        let field = match self.editing.take() {
+            Some(index) => &mut self.fields[index],
+            None => return Ok(EventState::NotConsumed),
+        };
+        if let Some(text) = text {
+            let text = match field.kind {
+                FieldKind::MultiLine { .. } => text.trim_end_matches('\n'),
+                _ => text.lines().next().unwrap_or_default(),
+            };
+            field.input.set_text(text);
+            field.error = None;
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn focused(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.clear();
+        self.status = None;
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use anyhow::Result;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{FormComponent, FormField, FormValues, FromForm, Input};
+    use crate::{
+        components::Component, keys::KeyConfig, queue::Queue,
+        ui::style::Theme,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Order {
+        item: String,
+        count: i64,
+        gift: bool,
+    }
+
+    impl FromForm for Order {
+        fn from_form(values: &FormValues) -> Result<Self> {
+            Ok(Self {
+                item: values.text("item")?,
+                count: values.number("count")?.unwrap_or(1),
+                gift: values.checked("gift")?,
+            })
+        }
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn type_text(form: &mut FormComponent, text: &str) {
+        for c in text.chars() {
+            form.event(key(KeyCode::Char(c))).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_mask_matches_display_width() {
+        let mut input = Input::default();
+        input.set_text("a密码");
+
+        assert_eq!(input.shown(Some('*')), "*****");
+        assert_eq!(input.cursor(), (0, 5));
+    }
+
+    #[test]
+    fn test_validate_and_submit() {
+        let mut form = FormComponent::new(
+            "Order",
+            &Queue::new(),
+            Rc::new(Theme::default()),
+            Rc::new(RefCell::new(KeyConfig::default())),
+        )
+            .field(
+                FormField::select(
+                    "item",
+                    "Item",
+                    vec!["tea".to_string(), "cake".to_string()],
+                )
+                .required(),
+            )
+            .field(FormField::number("count", "Count", 1, 9).required())
+            .field(FormField::checkbox("gift", "Gift"));
+        form.show().unwrap();
+
+        form.event(key(KeyCode::Right)).unwrap();
+        form.event(key(KeyCode::Enter)).unwrap();
+        assert!(form.take_submitted::<Order>().is_none());
+        assert!(form.fields[1].error.is_some());
+
+        type_text(&mut form, "x12");
+        form.event(key(KeyCode::Enter)).unwrap();
+        assert!(form.take_submitted::<Order>().is_none());
+
+        form.event(key(KeyCode::Backspace)).unwrap();
+        form.event(key(KeyCode::Tab)).unwrap();
+        type_text(&mut form, " ");
+        form.event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(
+            form.take_submitted::<Order>().unwrap().unwrap(),
+            Order {
+                item: "cake".to_string(),
+                count: 1,
+                gift: true,
+            }
+        );
+    }
+}
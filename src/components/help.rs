@@ -1,4 +1,4 @@
-use std::{borrow::Cow, cmp, convert::TryFrom};
+use std::{borrow::Cow, cell::Cell, cmp, convert::TryFrom};
 
 use anyhow::Result;
 // use asyncgit::hash;
@@ -15,18 +15,25 @@ use tui::{
 use itertools::Itertools;
 use ui::style::SharedTheme;
 
-use crate::{keys::SharedKeyConfig, strings, ui, version::Version};
+use crate::{
+    keys::{ActionId, KeyBinding, KeyContext, SharedKeyConfig},
+    strings, ui,
+};
 
 use super::{
     CommandBlocking, CommandInfo, Component, DrawableComponent,
-    EventState, visibility_blocking,
+    EventState, input_char, visibility_blocking,
 };
 
-///
+/// lists every action with its key and where it applies,
+/// typing narrows the list down
 pub struct HelpComponent {
-    cmds: Vec<CommandInfo>,
+    bindings: Vec<(KeyBinding, String)>,
+    filter: String,
+    filtered: Vec<usize>,
     visible: bool,
     selection: u16,
+    page_height: Cell<u16>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
 }
@@ -38,13 +45,12 @@ impl DrawableComponent for HelpComponent {
         _rect: Rect,
     ) -> Result<()> {
         if self.visible {
-            const SIZE: (u16, u16) = (65, 24);
-            let scroll_threshold = SIZE.1 / 3;
-            let scroll =
-                self.selection.saturating_sub(scroll_threshold);
-
-            let area =
-                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+            let size = f.size();
+            let area = ui::centered_rect_absolute(
+                size.width.saturating_sub(4).min(100),
+                size.height.saturating_sub(2),
+                size,
+            );
 
             f.render_widget(Clear, area);
             f.render_widget(
@@ -60,25 +66,45 @@ impl DrawableComponent for HelpComponent {
                 .horizontal_margin(1)
                 .direction(Direction::Vertical)
                 .constraints(
-                    [Constraint::Min(1), Constraint::Length(1)]
-                        .as_ref(),
+                    [
+                        Constraint::Length(2),
+                        Constraint::Min(1),
+                        Constraint::Length(1),
+                    ]
+                    .as_ref(),
                 )
                 .split(area);
 
             f.render_widget(
-                Paragraph::new(self.get_text())
+                Paragraph::new(Spans::from(strings::help_filter(
+                    &self.key_config,
+                    &self.filter,
+                )))
+                .block(Block::default().borders(Borders::BOTTOM))
+                .alignment(Alignment::Left),
+                chunks[0],
+            );
+
+            let height = chunks[1].height;
+            self.page_height.set(height);
+
+            let (text, selected_line) = self.get_text();
+            let scroll = selected_line.saturating_sub(height / 3);
+
+            f.render_widget(
+                Paragraph::new(text)
                     .scroll((scroll, 0))
                     .alignment(Alignment::Left),
-                chunks[0],
+                chunks[1],
             );
 
             f.render_widget(
                 Paragraph::new(Spans::from(vec![Span::styled(
-                    Cow::from(format!("gitui {}", Version::new(), )),
+                    Cow::from(strings::help_footer(&self.key_config)),
                     Style::default(),
                 )]))
                     .alignment(Alignment::Right),
-                chunks[1],
+                chunks[2],
             );
         }
 
@@ -104,6 +130,12 @@ impl Component for HelpComponent {
                 true,
             ));
 
+            out.push(CommandInfo::new(
+                strings::commands::scroll_page(&self.key_config),
+                true,
+                true,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::close_popup(&self.key_config),
                 true,
@@ -128,18 +160,36 @@ impl Component for HelpComponent {
     fn event(&mut self, ev: Event) -> Result<EventState> {
         if self.visible {
             if let Event::Key(e) = ev {
-                if e == self.key_config.exit_popup {
+                let keys = self.key_config.borrow().popup.clone();
+                let page = self.page_height.get().max(1);
+                if e == keys.exit_popup {
                     self.hide();
-                } else if e == self.key_config.move_down {
-                    self.move_selection(true);
-                } else if e == self.key_config.move_up {
-                    self.move_selection(false);
-                } else {}
+                } else if e == keys.scroll_down {
+                    self.move_selection(1);
+                } else if e == keys.scroll_up {
+                    self.move_selection(-1);
+                } else if e == keys.page_down {
+                    self.move_selection(i32::from(page));
+                } else if e == keys.page_up {
+                    self.move_selection(-i32::from(page));
+                } else if e == keys.move_top {
+                    self.selection = 0;
+                } else if e == keys.move_bottom {
+                    self.move_selection(i32::MAX);
+                } else if e
+                    == self.key_config.borrow().text_input.delete_back
+                {
+                    self.filter.pop();
+                    self.refilter();
+                } else if let Some(c) = input_char(e) {
+                    self.filter.push(c);
+                    self.refilter();
+                }
             }
 
             Ok(EventState::Consumed)
         } else if let Event::Key(k) = ev {
-            if k == self.key_config.open_help {
+            if k == self.key_config.borrow().global.open_help {
                 self.show()?;
                 Ok(EventState::Consumed)
             } else {
@@ -150,6 +200,17 @@ impl Component for HelpComponent {
         }
     }
 
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::Popup, "exit_popup") => self.hide(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
     fn is_visible(&self) -> bool {
         self.visible
     }
@@ -160,91 +221,138 @@ impl Component for HelpComponent {
 
     fn show(&mut self) -> Result<()> {
         self.visible = true;
+        self.bindings = Self::load_bindings(&self.key_config);
+        self.filter.clear();
+        self.refilter();
 
         Ok(())
     }
 }
 
 impl HelpComponent {
-    pub const fn new(
+    /// the listed actions come straight from the keymap tables
+    pub fn new(
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
+        let bindings = Self::load_bindings(&key_config);
+
         Self {
-            cmds: vec![],
+            filtered: (0..bindings.len()).collect(),
+            bindings,
+            filter: String::new(),
             visible: false,
             selection: 0,
+            page_height: Cell::new(0),
             theme,
             key_config,
         }
     }
-    ///
-    pub fn set_cmds(&mut self, cmds: Vec<CommandInfo>) {
-        self.cmds = cmds
-            .into_iter()
-            .filter(|e| !e.text.hide_help)
-            .collect::<Vec<_>>();
-        self.cmds.sort_by_key(|e| e.text.clone());
-        self.cmds.dedup_by_key(|e| e.text.clone());
-        // self.cmds.sort_by_key(|e| hash(&e.text.group));
+
+    /// read again on every `show` so rebinds are listed
+    fn load_bindings(
+        key_config: &SharedKeyConfig,
+    ) -> Vec<(KeyBinding, String)> {
+        key_config.borrow().help_entries()
     }
 
-    fn move_selection(&mut self, inc: bool) {
-        let mut new_selection = self.selection;
+    fn matches(&self, binding: &KeyBinding, scopes: &str) -> bool {
+        let filter = self.filter.to_lowercase();
+        [
+            binding.title.to_string(),
+            binding.desc.to_string(),
+            binding.path(),
+            binding.context.name().to_string(),
+            self.key_config.borrow().get_hint(binding.key),
+            scopes.to_string(),
+        ]
+        .iter()
+        .any(|s| s.to_lowercase().contains(&filter))
+    }
 
-        new_selection = if inc {
-            new_selection.saturating_add(1)
-        } else {
-            new_selection.saturating_sub(1)
-        };
-        new_selection = cmp::max(new_selection, 0);
+    fn refilter(&mut self) {
+        self.filtered = self
+            .bindings
+            .iter()
+            .enumerate()
+            .filter(|(_, (b, scopes))| self.matches(b, scopes))
+            .map(|(i, _)| i)
+            .collect();
+        self.selection = 0;
+    }
 
-        if let Ok(max) =
-        u16::try_from(self.cmds.len().saturating_sub(1))
-        {
-            self.selection = cmp::min(new_selection, max);
-        }
+    fn move_selection(&mut self, delta: i32) {
+        let new_selection = i64::from(self.selection) + i64::from(delta);
+        let max = self.filtered.len().saturating_sub(1);
+        let max = i64::try_from(max).unwrap_or(i64::MAX);
+
+        self.selection =
+            u16::try_from(cmp::max(0, cmp::min(new_selection, max)))
+                .unwrap_or(u16::MAX);
     }
 
-    fn get_text(&self) -> Vec<Spans> {
+    /// the lines to draw and the line of the selected entry
+    fn get_text(&self) -> (Vec<Spans<'_>>, u16) {
         let mut txt: Vec<Spans> = Vec::new();
+        let mut selected_line = 0_u16;
 
         let mut processed = 0_u16;
 
-        for (key, group) in
-        &self.cmds.iter().group_by(|e| e.text.group)
+        for (context, group) in &self
+            .filtered
+            .iter()
+            .map(|i| &self.bindings[*i])
+            .group_by(|(b, _)| b.context)
         {
             txt.push(Spans::from(Span::styled(
-                Cow::from(key.to_string()),
+                Cow::from(format!("-- {} --", context.name())),
                 Style::default().add_modifier(Modifier::REVERSED),
             )));
 
-            for command_info in group {
+            for (binding, scopes) in group {
                 let is_selected = self.selection == processed;
 
                 processed += 1;
 
+                if is_selected {
+                    selected_line =
+                        u16::try_from(txt.len()).unwrap_or(u16::MAX);
+                }
+
+                let name = format!(
+                    "{:<20} [{}]",
+                    binding.title,
+                    self.key_config.borrow().get_hint(binding.key)
+                );
                 txt.push(Spans::from(Span::styled(
                     Cow::from(if is_selected {
-                        format!(">{}", command_info.text.name)
+                        format!(">{}", name)
                     } else {
-                        format!(" {}", command_info.text.name)
+                        format!(" {}", name)
                     }),
                     self.theme.text(true, is_selected),
                 )));
 
                 if is_selected {
+                    txt.push(Spans::from(Span::styled(
+                        Cow::from(format!("  {}", binding.desc)),
+                        self.theme.text(true, is_selected),
+                    )));
                     txt.push(Spans::from(Span::styled(
                         Cow::from(format!(
-                            "  {}\n",
-                            command_info.text.desc
+                            "  {}",
+                            strings::help_scopes(
+                                &self.key_config,
+                                context.name(),
+                                scopes,
+                            )
                         )),
-                        self.theme.text(true, is_selected),
+                        self.theme.text(false, is_selected),
                     )));
                 }
             }
         }
 
-        txt
+        (txt, selected_line)
     }
 }
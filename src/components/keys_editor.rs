@@ -0,0 +1,389 @@
+use std::{borrow::Cow, cmp, convert::TryFrom, path::PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyEvent};
+use tui::{
+    backend::Backend,
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, Borders, BorderType, Clear, Paragraph},
+};
+
+use crate::{
+    keys::{
+        ActionId, KeyBinding, KeyConfig, KeyContext, KeyIssue,
+        SharedKeyConfig,
+    },
+    queue::{InternalEvent, Queue},
+    strings, ui,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    EventState, MsgKind, visibility_blocking,
+};
+
+/// lists every action and lets the user bind a new key to it,
+/// edits a copy of the config that is saved to `save_file`
+/// after every change, `None` keeps the changes in memory
+pub struct KeysEditorComponent {
+    config: KeyConfig,
+    save_file: Option<PathBuf>,
+    visible: bool,
+    selection: u16,
+    capturing: bool,
+    changed: bool,
+    status: Option<(String, bool)>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for KeysEditorComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (65, 24);
+            let scroll_threshold = SIZE.1 / 3;
+            let scroll =
+                self.selection.saturating_sub(scroll_threshold);
+
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title(strings::keys_editor_title(&self.key_config))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+                area,
+            );
+
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints(
+                    [Constraint::Min(1), Constraint::Length(1)]
+                        .as_ref(),
+                )
+                .split(area);
+
+            f.render_widget(
+                Paragraph::new(self.get_text())
+                    .scroll((scroll, 0))
+                    .alignment(Alignment::Left),
+                chunks[0],
+            );
+
+            f.render_widget(
+                Paragraph::new(Spans::from(self.get_status()))
+                    .alignment(Alignment::Left),
+                chunks[1],
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for KeysEditorComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible && !force_all {
+            out.clear();
+        }
+
+        if self.visible {
+            for text in [
+                strings::commands::scroll(&self.key_config),
+                strings::commands::keys_editor_rebind(&self.key_config),
+                strings::commands::keys_editor_reset(&self.key_config),
+            ] {
+                let cmd = CommandInfo::new(text, true, true);
+                out.push(if self.capturing {
+                    cmd.disabled_because(strings::DISABLED_CAPTURING)
+                } else {
+                    cmd
+                });
+            }
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        if !self.visible || force_all {
+            out.push(
+                CommandInfo::new(
+                    strings::commands::keys_editor_open(
+                        &self.key_config,
+                    ),
+                    true,
+                    true,
+                )
+                .order(99),
+            );
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let keys = self.key_config.borrow().popup.clone();
+                if self.capturing {
+                    self.capturing = false;
+                    self.status = None;
+                    if e != keys.exit_popup {
+                        self.rebind(Some(e))?;
+                    }
+                } else if e == keys.exit_popup {
+                    self.hide();
+                } else if e == keys.scroll_down {
+                    self.move_selection(true);
+                } else if e == keys.scroll_up {
+                    self.move_selection(false);
+                } else if e == keys.select {
+                    self.start_capture();
+                } else if e == keys.reset_item {
+                    self.rebind(None)?;
+                }
+            }
+
+            Ok(EventState::Consumed)
+        } else if let Event::Key(k) = ev {
+            if k == self.key_config.borrow().global.open_key_editor {
+                self.show()?;
+                Ok(EventState::Consumed)
+            } else {
+                Ok(EventState::NotConsumed)
+            }
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible || self.capturing {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::Popup, "exit_popup") => self.hide(),
+            (KeyContext::Popup, "select") => self.start_capture(),
+            (KeyContext::Popup, "reset_item") => self.rebind(None)?,
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.capturing = false;
+        self.status = None;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.config = self.key_config.borrow().clone();
+
+        Ok(())
+    }
+}
+
+impl KeysEditorComponent {
+    pub fn new(
+        save_file: Option<PathBuf>,
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        let config = key_config.borrow().clone();
+        Self {
+            config,
+            save_file,
+            visible: false,
+            selection: 0,
+            capturing: false,
+            changed: false,
+            status: None,
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// waiting for the key to bind, every key belongs to the editor
+    pub const fn is_capturing(&self) -> bool {
+        self.visible && self.capturing
+    }
+
+    /// the saved config once the popup got closed after a change
+    pub fn take_changed(&mut self) -> Option<KeyConfig> {
+        if self.changed && !self.visible {
+            self.changed = false;
+            Some(self.config.clone())
+        } else {
+            None
+        }
+    }
+
+    /// the next key pressed gets bound to the selected action
+    fn start_capture(&mut self) {
+        self.capturing = true;
+        self.status = Some((
+            strings::keys_editor_capture(
+                &self.key_config,
+                self.selected().title,
+            ),
+            false,
+        ));
+    }
+
+    /// a failed save only gets reported,
+    /// the change is applied anyway once the popup closes
+    fn save(&self) -> bool {
+        let file = match &self.save_file {
+            Some(file) => file.clone(),
+            None => return false,
+        };
+        match self.config.save(file) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("saving key config: {}", e);
+                self.queue.push(InternalEvent::Toast(
+                    MsgKind::Error,
+                    strings::toast_keys_not_saved(&self.key_config, &e),
+                ));
+                false
+            }
+        }
+    }
+
+    fn selected(&self) -> KeyBinding {
+        self.config.bindings()[self.selection as usize]
+    }
+
+    /// binds `key` to the selected action,
+    /// `None` restores the default binding
+    fn rebind(&mut self, key: Option<KeyEvent>) -> Result<()> {
+        let binding = self.selected();
+        let key = match key {
+            Some(key) => key,
+            None => match KeyConfig::default()
+                .bindings()
+                .into_iter()
+                .find(|b| b.path() == binding.path())
+            {
+                Some(default) => default.key,
+                None => return Ok(()),
+            },
+        };
+
+        if let Some(k) = self.config.binding_mut(&binding) {
+            *k = key;
+        }
+
+        let saved = self.save();
+        self.changed = true;
+
+        let path = binding.path();
+        let report = self.config.check_conflicts();
+        let warning = report
+            .issues
+            .iter()
+            .zip(report.lines(&self.config))
+            .find(|(issue, _)| match issue {
+                KeyIssue::Conflict { bindings, .. } => {
+                    bindings.iter().any(|b| b.path() == path)
+                }
+                KeyIssue::Shadowed {
+                    binding, by, ..
+                } => binding.path() == path || by.path() == path,
+            })
+            .map(|(_, line)| line);
+
+        self.status = Some(match warning {
+            Some(line) => (line, true),
+            None if saved => {
+                (strings::keys_editor_saved(&self.key_config), false)
+            }
+            None => {
+                (strings::keys_editor_unsaved(&self.key_config), false)
+            }
+        });
+
+        Ok(())
+    }
+
+    fn move_selection(&mut self, inc: bool) {
+        let mut new_selection = self.selection;
+
+        new_selection = if inc {
+            new_selection.saturating_add(1)
+        } else {
+            new_selection.saturating_sub(1)
+        };
+        new_selection = cmp::max(new_selection, 0);
+
+        if let Ok(max) =
+        u16::try_from(self.config.bindings().len().saturating_sub(1))
+        {
+            self.selection = cmp::min(new_selection, max);
+        }
+    }
+
+    fn get_status(&self) -> Vec<Span<'_>> {
+        match &self.status {
+            Some((msg, true)) => vec![Span::styled(
+                Cow::from(msg.as_str()),
+                self.theme.text_danger(),
+            )],
+            Some((msg, false)) => {
+                vec![Span::raw(Cow::from(msg.as_str()))]
+            }
+            None => vec![],
+        }
+    }
+
+    fn get_text(&self) -> Vec<Spans<'_>> {
+        self.config
+            .bindings()
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let is_selected = self.selection as usize == i;
+                let hint = if is_selected && self.capturing {
+                    "...".to_string()
+                } else {
+                    self.config.get_hint(b.key)
+                };
+                Spans::from(Span::styled(
+                    Cow::from(format!(
+                        "{}{:<11}{:<22}[{}]",
+                        if is_selected { ">" } else { " " },
+                        b.context.name(),
+                        b.title,
+                        hint
+                    )),
+                    self.theme.text(true, is_selected),
+                ))
+            })
+            .collect()
+    }
+}
@@ -1,7 +1,7 @@
 use std::borrow::BorrowMut;
 use std::cell::{Cell, RefCell};
 
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::Event;
 use log::info;
 use tui::backend::Backend;
 use tui::Frame;
@@ -10,8 +10,11 @@ use tui::style::{Color, Modifier, Style};
 use tui::text::Text;
 use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
 
-use crate::components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState};
-use crate::keys::SharedKeyConfig;
+use crate::components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState, MsgKind};
+use crate::keys::{ActionId, KeyContext, SharedKeyConfig};
+use crate::queue::{Action, InternalEvent, Queue};
+use crate::settings::SharedSettings;
+use crate::strings;
 use crate::ui::style::SharedTheme;
 
 pub struct ListComponent {
@@ -21,37 +24,83 @@ pub struct ListComponent {
     event_enable: bool,
     list_items: Vec<String>,
     state: RefCell<ListState>,
+    queue: Queue,
     key_config: SharedKeyConfig,
+    settings: SharedSettings,
 }
 
 impl Component for ListComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>, force_all: bool) -> CommandBlocking {
-        todo!()
+        if self.selected || force_all {
+            let navigate = CommandInfo::new(
+                strings::commands::list_navigate(&self.key_config),
+                true,
+                true,
+            );
+            let remove = CommandInfo::new(
+                strings::commands::list_remove_line(&self.key_config),
+                true,
+                true,
+            );
+            let remove_all = CommandInfo::new(
+                strings::commands::list_remove_all(&self.key_config),
+                !self.list_items.is_empty(),
+                true,
+            )
+            .hidden();
+            if !self.event_enable {
+                out.push(navigate.disabled_because(strings::DISABLED_NOT_FOCUSED));
+                out.push(remove.disabled_because(strings::DISABLED_NOT_FOCUSED));
+                out.push(remove_all.disabled_because(strings::DISABLED_NOT_FOCUSED));
+            } else if self.state.borrow().selected().is_none() {
+                out.push(navigate);
+                out.push(remove.disabled_because(strings::DISABLED_NOTHING_SELECTED));
+                out.push(remove_all);
+            } else {
+                out.push(navigate);
+                out.push(remove);
+                out.push(remove_all);
+            }
+        }
+        CommandBlocking::PassingOn
     }
 
     fn event(&mut self, ev: Event) -> anyhow::Result<EventState> {
         if self.is_visible() && self.event_enable && self.selected {
             if let Event::Key(key) = ev {
-                match key.code {
-                    KeyCode::Down => {
-                        self.next();
-                        return Ok(EventState::Consumed);
-                    }
-                    KeyCode::Up => {
-                        self.previous();
-                        return Ok(EventState::Consumed);
-                    }
-                    _ => {}
+                if key == self.key_config.borrow().list.move_down {
+                    self.next();
+                    return Ok(EventState::Consumed);
                 }
-
-                if key == self.key_config.stash_drop {
-                    self.remove_line();
+                if key == self.key_config.borrow().list.move_up {
+                    self.previous();
+                    return Ok(EventState::Consumed);
+                }
+                if key == self.key_config.borrow().list.remove_line {
+                    self.request_remove_line();
+                    return Ok(EventState::Consumed);
+                }
+                if key == self.key_config.borrow().list.remove_all {
+                    self.request_remove_all();
+                    return Ok(EventState::Consumed);
                 }
             }
         }
         Ok(EventState::NotConsumed)
     }
 
+    fn run_action(&mut self, action: ActionId) -> anyhow::Result<EventState> {
+        if !(self.is_visible() && self.event_enable && self.selected) {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::List, "remove_line") => self.request_remove_line(),
+            (KeyContext::List, "remove_all") => self.request_remove_all(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
     fn focused(&self) -> bool {
         self.selected
     }
@@ -90,8 +139,10 @@ impl DrawableComponent for ListComponent {
 
 impl ListComponent {
     pub fn new(
+        queue: &Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        settings: SharedSettings,
     ) -> Self {
         Self {
             title: "".to_string(),
@@ -100,14 +151,18 @@ impl ListComponent {
             event_enable: true,
             list_items: vec!["1".to_string(), "2".to_string()],
             state: RefCell::new(ListState::default()),
+            queue: queue.clone(),
             key_config,
+            settings,
         }
     }
 
     pub fn new_with_title(
         title: String,
+        queue: &Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        settings: SharedSettings,
     ) -> Self {
         Self {
             title,
@@ -116,7 +171,9 @@ impl ListComponent {
             event_enable: true,
             list_items: vec!["1".to_string(), "2".to_string()],
             state: RefCell::new(ListState::default()),
+            queue: queue.clone(),
             key_config,
+            settings,
         }
     }
 
@@ -128,6 +185,9 @@ impl ListComponent {
         self.event_enable = false;
     }
     pub fn next(&mut self) {
+        if self.list_items.is_empty() {
+            return;
+        }
         let i = match self.state.get_mut().selected() {
             Some(i) => {
                 if i >= self.list_items.len() - 1 {
@@ -148,6 +208,9 @@ impl ListComponent {
 
 
     pub fn previous(&mut self) {
+        if self.list_items.is_empty() {
+            return;
+        }
         let i = match self.state.get_mut().selected() {
             Some(i) => {
                 if i == 0 {
@@ -165,22 +228,72 @@ impl ListComponent {
         self.state.get_mut().select(None);
     }
 
-    fn remove_line(&mut self) {
-        match self.state.get_mut().selected() {
-            Some(idx) => {
-                self.list_items.swap_remove(idx);
-                info!("idx is {}",idx);
-                info!("items length {}",self.list_items.len());
-                if idx >= self.list_items.len() {
-                    if self.list_items.len() == 0 {
-                        self.unselected();
-                        return;
-                    }
-                    self.previous();
+    fn request_remove_line(&mut self) {
+        if let Some(index) = self.state.get_mut().selected() {
+            self.request(Action::RemoveLine {
+                list: self.title.clone(),
+                index,
+            });
+        }
+    }
+
+    fn request_remove_all(&mut self) {
+        if !self.list_items.is_empty() {
+            self.request(Action::RemoveAllLines {
+                list: self.title.clone(),
+            });
+        }
+    }
+
+    /// runs `action` right away or asks first if removals are to be confirmed
+    fn request(&mut self, action: Action) {
+        if self.settings.confirm_removal {
+            self.queue.push(InternalEvent::ConfirmAction(action));
+        } else {
+            self.action_confirmed(&action);
+        }
+    }
+
+    /// runs a confirmed action, `false` if it is meant for another list
+    pub fn action_confirmed(&mut self, action: &Action) -> bool {
+        match action {
+            Action::RemoveLine { list, index } if *list == self.title => {
+                self.remove_line(*index);
+                self.queue.push(InternalEvent::Toast(
+                    MsgKind::Success,
+                    strings::toast_line_removed(&self.key_config, list, *index),
+                ));
+                true
+            }
+            Action::RemoveAllLines { list } if *list == self.title => {
+                self.list_items.clear();
+                self.unselected();
+                self.queue.push(InternalEvent::Toast(
+                    MsgKind::Success,
+                    strings::toast_lines_cleared(&self.key_config, list),
+                ));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn remove_line(&mut self, idx: usize) {
+        if idx >= self.list_items.len() {
+            return;
+        }
+        self.list_items.swap_remove(idx);
+        info!("idx is {}",idx);
+        info!("items length {}",self.list_items.len());
+        if let Some(selected) = self.state.get_mut().selected() {
+            if selected >= self.list_items.len() {
+                if self.list_items.len() == 0 {
+                    self.unselected();
+                    return;
                 }
+                self.previous();
             }
-            None => {}
-        };
+        }
     }
     pub fn list_item_add(&mut self, str: String) {
         self.list_items.push(str);
@@ -1,89 +1,192 @@
-use std::collections::HashMap;
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
 
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode};
-use log::info;
+use crossterm::event::Event;
+use ron::ser::{PrettyConfig, to_string_pretty};
+use serde::{Deserialize, Serialize};
 use tui::backend::Backend;
 use tui::Frame;
-use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use tui::layout::Rect;
 use tui::style::{Color, Style};
-use tui::widgets::{Block, Borders, Clear, Paragraph};
-use unicode_width::UnicodeWidthStr;
 
-use crate::{components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState, visibility_blocking}, strings, ui};
-use crate::keys::SharedKeyConfig;
+use crate::{
+    args::get_app_config_path,
+    auth::{Authenticator, Credentials, Identity},
+    components::{
+        CommandBlocking, CommandInfo, Component, DrawableComponent,
+        EventState, FieldValue, FormComponent, FormField, FormValues,
+        FromForm, visibility_blocking,
+    },
+    queue::Queue,
+    spinner::Spinner,
+    strings,
+};
+use crate::keys::{ActionId, KeyContext, SharedKeyConfig};
+use crate::ui::style::SharedTheme;
 
-enum Focus {
-    UserName,
-    Password,
+/// wrong attempts before the next one has to wait
+const FREE_ATTEMPTS: u32 = 3;
+/// the wait never grows past this
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// wait before the next attempt after `failures` wrong ones,
+/// doubles with every failure past the free attempts
+fn backoff(failures: u32) -> Duration {
+    if failures < FREE_ATTEMPTS {
+        return Duration::ZERO;
+    }
+    let exp = (failures - FREE_ATTEMPTS).min(16);
+    (Duration::from_secs(5) * 2_u32.pow(exp)).min(MAX_BACKOFF)
+}
+
+/// a password typed with caps lock on is all upper case
+fn looks_like_caps_lock(text: &str) -> bool {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() > 1 && letters.iter().all(|c| c.is_uppercase())
 }
 
-pub struct Input {
-    text: String,
-    showtext: String,
-    input_position: usize,
-    cursor_position: usize,
+/// the username the dialog fills in, stored in `login.ron`
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct RememberedLogin {
+    pub remember: bool,
+    pub username: Option<String>,
 }
 
-impl Input {
-    pub fn new() -> Self {
-        Input {
-            text: "".to_string(),
-            showtext: "".to_string(),
-            input_position: 0,
-            cursor_position: 0,
+impl RememberedLogin {
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("login.ron"))
+    }
+
+    pub fn save(&self, file: PathBuf) -> Result<()> {
+        let mut file = File::create(file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    /// a missing file remembers nothing
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file)
+        } else {
+            Ok(Self::default())
         }
     }
+}
+
+/// wrong attempts and the lock they caused, stored in `login_backoff.ron`
+/// so neither a new dialog nor a restart starts the count over
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct LoginBackoff {
+    failures: u32,
+    locked_until: Option<SystemTime>,
+}
 
-    fn clear(&mut self) {
-        self.text.clear();
-        self.showtext.clear();
-        self.cursor_position = 0;
-        self.input_position = 0;
+impl LoginBackoff {
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("login_backoff.ron"))
     }
 
-    fn cursor_left(&mut self) -> bool {
-        if self.cursor_position > 0 {
-            let mut index = self.input_position - 1;
-            while index > 0
-                && !self.text.is_char_boundary(index) {
-                index -= 1;
-            }
-            if self.input_position - index > 1 {
-                self.cursor_position -= 2;
-            } else {
-                self.cursor_position -= 1;
-            }
-            self.input_position = index;
+    pub fn save(&self, file: PathBuf) -> Result<()> {
+        let mut file = File::create(file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
 
-            return true;
+    /// a missing file means no failed attempts
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file)
+        } else {
+            Ok(Self::default())
         }
-        return false;
     }
 
-    fn cursor_right(&mut self) {
-        if self.cursor_position < self.text.width() {
-            let mut index = self.input_position.saturating_add(1);
-            while index < self.text.len()
-                && !self.text.is_char_boundary(index) {
-                index += 1;
-            }
-            if index - self.input_position > 1 {
-                self.cursor_position += 2;
-            } else {
-                self.cursor_position += 1;
-            }
-            self.input_position = index;
+    /// counts a wrong attempt and locks once the free ones are used up
+    fn fail(&mut self, now: SystemTime) {
+        self.failures = self.failures.saturating_add(1);
+        let wait = backoff(self.failures);
+        if wait > Duration::ZERO {
+            self.locked_until = now.checked_add(wait);
         }
     }
+
+    /// how long the next attempt has to wait at `now`
+    fn wait(&self, now: SystemTime) -> Option<Duration> {
+        self.locked_until
+            .and_then(|until| until.duration_since(now).ok())
+            .filter(|wait| *wait > Duration::ZERO)
+    }
+}
+
+/// what the login form submits
+struct LoginInput {
+    credentials: Credentials,
+    remember: bool,
+}
+
+impl FromForm for LoginInput {
+    fn from_form(values: &FormValues) -> Result<Self> {
+        Ok(Self {
+            credentials: Credentials {
+                username: values.text("username")?,
+                password: values.secret("password")?,
+            },
+            remember: values.checked("remember")?,
+        })
+    }
+}
+
+/// outcome of the last login attempt, shown below the form
+enum Status {
+    Idle,
+    Checking(Receiver<Result<Option<Identity>>>),
+    Failed(String),
+    Success(Identity),
 }
 
+/// the login dialog, a form that asks an `Authenticator` on submit
 pub struct LoginComponent {
-    visible: bool,
-    focus: Focus,
-    username: Input,
-    password: Input,
-    message: HashMap<String, String>,
+    form: FormComponent,
+    backoff: LoginBackoff,
+    /// where `backoff` gets stored, `None` keeps it in memory
+    backoff_file: Option<PathBuf>,
+    remembered: RememberedLogin,
+    /// where `remembered` gets stored, `None` keeps it in memory
+    remembered_file: Option<PathBuf>,
+    authenticator: Option<Arc<dyn Authenticator>>,
+    status: Status,
+    spinner: Spinner,
+    identity: Option<Identity>,
     key_config: SharedKeyConfig,
 }
 
@@ -91,178 +194,36 @@ impl DrawableComponent for LoginComponent {
     fn draw<B: Backend>(
         &self,
         f: &mut Frame<B>,
-        _rect: Rect,
+        rect: Rect,
     ) -> anyhow::Result<()> {
-        if self.is_visible() {
-            const SIZE: (u16, u16) = (40, 8);
-            let area = ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
-
-            let userinput = Paragraph::new(self.username.text.as_ref())
-                .style(Style::default().fg(Color::Yellow))
-                .block(Block::default().borders(Borders::ALL).title("UserName"));
-            let passwordinput = Paragraph::new(self.password.showtext.as_ref())
-                .style(Style::default().fg(Color::Yellow))
-                .block(Block::default().borders(Borders::ALL).title("Password"));
-
-            let login_block = Block::default().title("Login").borders(Borders::ALL);
-            f.render_widget(Clear, area);
-            f.render_widget(login_block, area);
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .margin(1)
-                .constraints(
-                    [
-                        Constraint::Percentage(50),
-                        Constraint::Percentage(50),
-                    ]
-                        .as_ref(),
-                )
-                .split(area);
-
-            f.render_widget(userinput, chunks[0]);
-            f.render_widget(passwordinput, chunks[1]);
-
-            match self.focus {
-                Focus::UserName => {
-                    f.set_cursor(
-                        // Put cursor past the end of the input text
-                        chunks[0].x + self.username.cursor_position as u16 + 1,
-                        // Move one line down, from the border to the input line
-                        chunks[0].y + 1,
-                    )
-                }
-
-                Focus::Password => {
-                    f.set_cursor(
-                        // Put cursor past the end of the input text
-                        chunks[1].x + self.password.cursor_position as u16 + 1,
-                        // Move one line down, from the border to the input line
-                        chunks[1].y + 1,
-                    )
-                }
-            }
-        }
-        Ok(())
+        self.form.draw(f, rect)
     }
 }
 
 impl Component for LoginComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>, force_all: bool) -> CommandBlocking {
-        if self.is_visible() || force_all {}
+        self.form.commands(out, force_all);
         visibility_blocking(self)
     }
 
     fn event(&mut self, ev: Event) -> anyhow::Result<EventState> {
         if self.is_visible() {
             if let Event::Key(key) = ev {
-                match key.code {
-                    KeyCode::Enter => {
-                        self.message.clear();
-                        if !self.username.text.is_empty() {
-                            self.message.insert("username".to_string(), self.username.text.clone());
-                            self.message.insert("password".to_string(), self.password.text.clone());
-                            self.clear();
-                        }
-                    }
-                    KeyCode::Char(c) => {
-                        match self.focus {
-                            Focus::UserName => {
-                                if self.username.text.width() == self.username.cursor_position {
-                                    self.username.text.push(c);
-                                    self.username.input_position = self.username.text.len();
-                                    self.username.cursor_position = self.username.text.width();
-                                }
-
-                                if self.username.text.width() > self.username.cursor_position {
-                                    self.username.text.insert(self.username.input_position, c);
-                                    self.username.cursor_right();
-                                }
-                            }
-                            Focus::Password => {
-                                if self.password.text.width() == self.password.cursor_position {
-                                    self.password.text.push(c);
-                                    self.password.input_position = self.password.text.len();
-                                    self.password.cursor_position = self.password.text.width();
-                                    self.password.showtext.push('*');
-                                }
-
-                                if self.password.text.width() > self.password.cursor_position {
-                                    self.password.text.insert(self.password.input_position, c);
-                                    self.password.cursor_right();
-                                    self.password.showtext.push('*');
-                                }
-                            }
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        match self.focus {
-                            Focus::UserName => {
-                                if self.username.cursor_left() {
-                                    self.username.text.remove(self.username.input_position);
-                                };
-                            }
-                            Focus::Password => {
-                                if self.password.cursor_left() {
-                                    self.password.text.remove(self.password.input_position);
-                                    self.password.showtext.pop();
-                                };
-                            }
-                        }
-                    }
-
-                    KeyCode::Delete => {
-                        match self.focus {
-                            Focus::UserName => {
-                                if self.username.text.len() > self.username.input_position {
-                                    self.username.text.remove(self.username.input_position);
-                                }
-                            }
-                            Focus::Password => {
-                                if self.password.text.len() > self.password.input_position {
-                                    self.password.text.remove(self.password.input_position);
-                                    self.password.showtext.pop();
-                                }
-                            }
-                        }
-                    }
-                    KeyCode::Left => {
-                        match self.focus {
-                            Focus::UserName => {
-                                self.username.cursor_left();
-                            }
-                            Focus::Password => {
-                                self.password.cursor_left();
-                            }
-                        }
-                    }
-                    KeyCode::Right => {
-                        match self.focus {
-                            Focus::UserName => {
-                                self.username.cursor_right();
-                            }
-                            Focus::Password => {
-                                self.password.cursor_right();
-                            }
-                        }
-                    }
-                    KeyCode::Up => {
-                        self.toggle_focus();
-                    }
-                    KeyCode::Down => {
-                        self.toggle_focus();
-                    }
-                    KeyCode::Tab => {}
-                    KeyCode::Esc => {
-                        self.clear();
-                        self.hide();
-                    }
-                    _ => { return Ok(EventState::NotConsumed); }
+                let keys = self.key_config.borrow().text_input.clone();
+                if key == self.key_config.borrow().global.exit {
+                    return Ok(EventState::NotConsumed);
+                }
+                if self.status_event(key == keys.confirm, key == keys.cancel) {
+                    return Ok(EventState::Consumed);
                 }
-                return Ok(EventState::Consumed);
             }
+
+            let res = self.form.event(ev)?;
+            self.after_form()?;
+            return Ok(res);
         } else {
             if let Event::Key(key) = ev {
-                if key == self.key_config.login {
+                if key == self.key_config.borrow().tab.login {
                     self.show()?;
                     return Ok(EventState::Consumed);
                 }
@@ -273,59 +234,409 @@ impl Component for LoginComponent {
         Ok(EventState::NotConsumed)
     }
 
+    fn run_action(&mut self, action: ActionId) -> anyhow::Result<EventState> {
+        if !self.is_visible() {
+            if action == ActionId::new(KeyContext::Tab, "login") {
+                self.show()?;
+                return Ok(EventState::Consumed);
+            }
+            return Ok(EventState::NotConsumed);
+        }
+        if self.status_event(
+            action == ActionId::new(KeyContext::TextInput, "confirm"),
+            action == ActionId::new(KeyContext::TextInput, "cancel"),
+        ) {
+            return Ok(EventState::Consumed);
+        }
+
+        let res = self.form.run_action(action)?;
+        self.after_form()?;
+        Ok(res)
+    }
+
+    fn edited(&mut self, text: Option<&str>) -> anyhow::Result<EventState> {
+        self.form.edited(text)
+    }
+
     fn is_visible(&self) -> bool {
-        self.visible
+        self.form.is_visible()
+    }
+
+    fn focused(&self) -> bool {
+        self.form.is_visible()
     }
 
     fn hide(&mut self) {
-        self.clear();
-        self.visible = false;
+        self.form.hide();
+        self.status = Status::Idle;
     }
 
+    /// fills in a remembered username and starts at the password then
     fn show(&mut self) -> Result<()> {
-        self.visible = true;
-        Ok(())
+        if !self.is_visible() {
+            if let Some(username) = &self.remembered.username {
+                self.form.set_text("username", username);
+                self.form.focus_field("password");
+            }
+        }
+        self.sync_status();
+        self.form.show()
     }
 }
 
 impl LoginComponent {
+    /// without an authenticator every login attempt fails with a hint
     pub fn new(
+        authenticator: Option<Arc<dyn Authenticator>>,
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        let remembered_file = RememberedLogin::get_config_file()
+            .map_err(|e| log::warn!("remembered login: {}", e))
+            .ok();
+        let backoff_file = LoginBackoff::get_config_file()
+            .map_err(|e| log::warn!("login back-off: {}", e))
+            .ok();
+
+        Self::with_files(
+            remembered_file,
+            backoff_file,
+            authenticator,
+            queue,
+            theme,
+            key_config,
+        )
+    }
+
+    /// like `new` but reads and writes `login.ron` and
+    /// `login_backoff.ron` at the given paths, `None` touches no file
+    pub fn with_files(
+        remembered_file: Option<PathBuf>,
+        backoff_file: Option<PathBuf>,
+        authenticator: Option<Arc<dyn Authenticator>>,
+        queue: &Queue,
+        theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
+        let remembered = remembered_file
+            .clone()
+            .map(RememberedLogin::init)
+            .transpose()
+            .unwrap_or_else(|e| {
+                log::warn!("remembered login: {}", e);
+                None
+            })
+            .unwrap_or_default();
+
+        let backoff = backoff_file
+            .clone()
+            .map(LoginBackoff::init)
+            .transpose()
+            .unwrap_or_else(|e| {
+                log::warn!("login back-off: {}", e);
+                None
+            })
+            .unwrap_or_default();
+
+        let caps_hint = strings::login_caps_hint(&key_config);
+        let form = FormComponent::new("Login", queue, theme, key_config.clone())
+            .field(FormField::text("username", "UserName").required())
+            .field(
+                FormField::password("password", "Password")
+                    .required()
+                    .hint(move |value| match value {
+                        FieldValue::Secret(secret)
+                            if looks_like_caps_lock(secret.expose()) =>
+                        {
+                            Some(caps_hint.clone())
+                        }
+                        _ => None,
+                    }),
+            )
+            .field(
+                FormField::checkbox(
+                    "remember",
+                    &strings::login_remember(&key_config),
+                )
+                .checked(remembered.remember),
+            )
+            .submit_label(&strings::login_submit(&key_config));
+
         Self {
-            visible: false,
-            focus: Focus::UserName,
-            username: Input::new(),
-            password: Input::new(),
-            message: HashMap::new(),
+            form,
+            backoff,
+            backoff_file,
+            remembered,
+            remembered_file,
+            authenticator,
+            status: Status::Idle,
+            spinner: Spinner::default(),
+            identity: None,
             key_config,
         }
     }
 
-    pub fn get_msg(&mut self) -> HashMap<String, String> {
-        let mut hash = HashMap::new();
-        if !self.message.is_empty() {
-            hash = self.message.clone();
-            self.message.clear();
+    /// the identity of a login that succeeded since the last call
+    pub fn take_identity(&mut self) -> Option<Identity> {
+        self.identity.take()
+    }
+
+    /// turns the spinner, counts down the back-off
+    /// and picks up the answer of the authenticator
+    pub fn on_tick(&mut self) {
+        self.form.on_tick();
+
+        let res = match &self.status {
+            Status::Checking(rx) => match rx.try_recv() {
+                Ok(res) => Some(res),
+                Err(TryRecvError::Empty) => {
+                    self.spinner.update();
+                    None
+                }
+                Err(TryRecvError::Disconnected) => {
+                    Some(Err(anyhow::anyhow!("authenticator stopped")))
+                }
+            },
+            _ => None,
+        };
+
+        match res {
+            Some(Ok(Some(identity))) => {
+                self.set_backoff(LoginBackoff::default());
+                let remember = self.form.value("remember")
+                    == Some(FieldValue::Checked(true));
+                self.remember(remember, &identity.username);
+                self.form.clear();
+                self.identity = Some(identity.clone());
+                self.status = Status::Success(identity);
+            }
+            Some(Ok(None)) => {
+                self.fail_attempt();
+                self.form.set_text("password", "");
+                self.form.focus_field("password");
+                self.status =
+                    Status::Failed(strings::login_failed(&self.key_config));
+            }
+            Some(Err(e)) => {
+                log::error!("login failed: {:#}", e);
+                self.status = Status::Failed(strings::login_error(
+                    &self.key_config,
+                    &format!("{:#}", e),
+                ));
+            }
+            None => (),
+        }
+        self.sync_status();
+    }
+
+    /// asks the authenticator on a thread of its own,
+    /// backends may hash slowly or wait on the network
+    /// while a check runs or after it succeeded only confirm and
+    /// cancel do something, `true` if the status took the input
+    fn status_event(&mut self, confirm: bool, cancel: bool) -> bool {
+        match self.status {
+            Status::Checking(_) => {
+                // the thread finishes on its own, its answer is dropped,
+                // so the attempt counts as wrong or cancelling would
+                // get around the back-off
+                if cancel {
+                    self.fail_attempt();
+                    self.status = Status::Idle;
+                    self.sync_status();
+                }
+                true
+            }
+            Status::Success(_) => {
+                if confirm || cancel {
+                    self.hide();
+                }
+                true
+            }
+            Status::Idle | Status::Failed(_) => false,
+        }
+    }
+
+    /// picks up what the form got submitted or closed with
+    fn after_form(&mut self) -> anyhow::Result<()> {
+        if let Some(input) = self.form.take_submitted::<LoginInput>() {
+            self.submit(input?);
+        }
+        if !self.form.is_visible() {
+            self.hide();
+        }
+        Ok(())
+    }
+
+    fn submit(&mut self, input: LoginInput) {
+        if self.wait().is_some() {
+            return;
         }
+        if !input.remember {
+            self.remember(false, "");
+        }
+        let authenticator = match &self.authenticator {
+            Some(authenticator) => authenticator.clone(),
+            None => {
+                self.status =
+                    Status::Failed(strings::login_no_backend(&self.key_config));
+                self.sync_status();
+                return;
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let credentials = input.credentials;
+        thread::spawn(move || {
+            // nobody listens anymore if the attempt got cancelled
+            let _ = tx.send(authenticator.authenticate(&credentials));
+        });
+        self.status = Status::Checking(rx);
+        self.sync_status();
+    }
 
-        return hash;
+    /// hands the current status to the form, which draws it
+    fn sync_status(&mut self) {
+        let status = match &self.status {
+            Status::Checking(_) => Some((
+                format!(
+                    "{} {}",
+                    self.spinner.get(),
+                    strings::login_checking(&self.key_config)
+                ),
+                Style::default(),
+            )),
+            Status::Success(identity) => Some((
+                strings::msg_logged_in(&self.key_config, identity.name()),
+                Style::default().fg(Color::Green),
+            )),
+            _ if self.wait().is_some() => Some((
+                strings::login_locked(
+                    &self.key_config,
+                    self.wait().unwrap_or_default().as_secs() + 1,
+                ),
+                Style::default().fg(Color::Red),
+            )),
+            Status::Failed(msg) => {
+                Some((msg.clone(), Style::default().fg(Color::Red)))
+            }
+            Status::Idle => None,
+        };
+        self.form.set_status(status);
+        self.form.set_enabled(matches!(
+            self.status,
+            Status::Idle | Status::Failed(_)
+        ));
+    }
+
+    /// how long the next attempt has to wait after too many wrong ones
+    fn wait(&self) -> Option<Duration> {
+        self.backoff.wait(SystemTime::now())
     }
 
-    fn clear(&mut self) {
-        self.username.clear();
-        self.password.clear();
-        self.focus = Focus::UserName;
+    fn fail_attempt(&mut self) {
+        let mut backoff = self.backoff.clone();
+        backoff.fail(SystemTime::now());
+        self.set_backoff(backoff);
     }
 
-    fn toggle_focus(&mut self) {
-        match self.focus {
-            Focus::UserName => {
-                self.focus = Focus::Password
+    /// keeps the back-off and stores it if it changed
+    fn set_backoff(&mut self, backoff: LoginBackoff) {
+        if backoff == self.backoff {
+            return;
+        }
+        self.backoff = backoff;
+        if let Some(file) = &self.backoff_file {
+            if let Err(e) = self.backoff.save(file.clone()) {
+                log::error!("failed to save login_backoff.ron: {:#}", e);
             }
-            Focus::Password => {
-                self.focus = Focus::UserName
+        }
+    }
+
+    /// stores or forgets the username, depending on the checkbox
+    fn remember(&mut self, remember: bool, username: &str) {
+        let remembered = RememberedLogin {
+            remember,
+            username: remember.then(|| username.to_string()),
+        };
+        if remembered == self.remembered {
+            return;
+        }
+        self.remembered = remembered;
+        if let Some(file) = &self.remembered_file {
+            if let Err(e) = self.remembered.save(file.clone()) {
+                log::error!("failed to save login.ron: {:#}", e);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::{mpsc, Arc, Mutex},
+        time::Duration,
+    };
+
+    use anyhow::Result;
+
+    use super::{
+        backoff, looks_like_caps_lock, LoginComponent, LoginInput,
+        MAX_BACKOFF,
+    };
+    use crate::{
+        auth::{Authenticator, Credentials, Identity},
+        keys::KeyConfig,
+        queue::Queue,
+    };
+
+    /// never answers until the test ends
+    struct Hanging(Mutex<mpsc::Receiver<()>>);
+
+    impl Authenticator for Hanging {
+        fn authenticate(&self, _: &Credentials) -> Result<Option<Identity>> {
+            let _ = self.0.lock().unwrap().recv();
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        assert_eq!(backoff(2), Duration::ZERO);
+        assert_eq!(backoff(3), Duration::from_secs(5));
+        assert_eq!(backoff(4), Duration::from_secs(10));
+        assert_eq!(backoff(40), MAX_BACKOFF);
+        assert!(looks_like_caps_lock("SECRET1"));
+        assert!(!looks_like_caps_lock("Secret1"));
+    }
+
+    #[test]
+    fn test_cancelled_check_counts() {
+        let (_tx, rx) = mpsc::channel();
+        let mut login = LoginComponent::with_files(
+            None,
+            None,
+            Some(Arc::new(Hanging(Mutex::new(rx)))),
+            &Queue::new(),
+            Rc::default(),
+            Rc::new(RefCell::new(KeyConfig::default())),
+        );
+
+        for _ in 0..3 {
+            assert!(login.wait().is_none());
+            login.submit(LoginInput {
+                credentials: Credentials {
+                    username: "ann".to_string(),
+                    password: "guess".into(),
+                },
+                remember: true,
+            });
+            assert!(login.status_event(false, true));
+        }
+
+        assert_eq!(login.backoff.failures, 3);
+        assert!(login.wait().is_some());
+    }
+}
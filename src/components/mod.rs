@@ -1,7 +1,10 @@
 use std::convert::From;
 
 use anyhow::Result;
-use crossterm::event::Event;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 use tui::{
     backend::Backend,
     Frame,
@@ -11,21 +14,52 @@ use tui::{
 };
 
 pub use command::{CommandInfo, CommandText};
+pub use checkbox::CheckboxComponent;
+pub use confirm::ConfirmComponent;
+pub use dropdown::DropdownComponent;
+pub use external_editor::ExternalEditor;
+pub use focus::FocusRing;
+pub use form::{
+    FieldValue, FormComponent, FormField, FormValues, FromForm,
+};
 pub use help::HelpComponent;
+pub use keys_editor::KeysEditorComponent;
 pub use list::ListComponent;
 pub use login::LoginComponent;
+pub use msg::{MsgComponent, MsgKind};
+pub use palette::CommandPaletteComponent;
 pub use popinput::PopInputComponent;
+pub use radio::RadioGroupComponent;
 pub use search::InputMode;
 pub use search::SearchComponent;
+pub use spin_box::SpinBoxComponent;
+pub use textarea::{TextAreaComponent, TextAreaConfig};
+pub use toast::{ToastComponent, ToastConfig};
 
-use crate::ui::style::Theme;
+use crate::{
+    keys::ActionId,
+    ui::{style::Theme, UiBackend},
+};
 
+mod checkbox;
 mod command;
+mod confirm;
+mod dropdown;
+mod external_editor;
+mod focus;
+mod form;
 mod help;
+mod keys_editor;
 mod search;
 mod list;
 mod popinput;
 mod login;
+mod msg;
+mod palette;
+mod radio;
+mod spin_box;
+mod textarea;
+mod toast;
 
 
 /// creates accessors for a list of components
@@ -49,48 +83,36 @@ macro_rules! accessors {
     };
 }
 
-/// creates a function to determine if any popup is visible
-#[macro_export]
-macro_rules! any_popup_visible {
-    ($self:ident, [$($element:ident),+]) => {
-        fn any_popup_visible(& $self) -> bool{
-            ($($self.$element.is_visible()) || +)
+/// character to insert into a text input, if `key` is plain typing
+pub fn input_char(key: KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c)
+            if key.modifiers.is_empty()
+                || key.modifiers == KeyModifiers::SHIFT =>
+        {
+            Some(c)
         }
-    };
+        _ => None,
+    }
 }
 
-/// creates the draw popup function
-#[macro_export]
-macro_rules! draw_popups {
-    ($self:ident, [$($element:ident),+]) => {
-        fn draw_popups<B: Backend>(& $self, mut f: &mut Frame<B>) -> Result<()>{
-            //TODO: move the layout part out and feed it into `draw_popups`
-            let size = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Min(1),
-                    Constraint::Length($self.cmdbar.borrow().height()),
-                ]
-                .as_ref(),
-            )
-            .split(f.size())[0];
-
-            ($($self.$element.draw(&mut f, size)?) , +);
-
-            return Ok(());
+/// cell of a left click that lands inside `area`
+pub fn clicked(ev: Event, area: Rect) -> Option<(u16, u16)> {
+    match ev {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) if column >= area.x
+            && column < area.right()
+            && row >= area.y
+            && row < area.bottom() =>
+        {
+            Some((column, row))
         }
-    };
-}
-
-/// simply calls
-/// any_popup_visible!() and draw_popups!() macros
-#[macro_export]
-macro_rules! setup_popups {
-    ($self:ident, [$($element:ident),+]) => {
-        crate::any_popup_visible!($self, [$($element),+]);
-        crate::draw_popups!($self, [ $($element),+ ]);
-    };
+        _ => None,
+    }
 }
 
 /// returns `true` if event was consumed
@@ -107,6 +129,34 @@ pub fn event_pump(
     Ok(EventState::NotConsumed)
 }
 
+/// like `event_pump` for an action picked in the command palette
+pub fn action_pump(
+    action: ActionId,
+    components: &mut [&mut dyn Component],
+) -> Result<EventState> {
+    for c in components {
+        if c.run_action(action)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+    }
+
+    Ok(EventState::NotConsumed)
+}
+
+/// like `event_pump` for what the external editor returned
+pub fn edited_pump(
+    text: Option<&str>,
+    components: &mut [&mut dyn Component],
+) -> Result<EventState> {
+    for c in components {
+        if c.edited(text)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+    }
+
+    Ok(EventState::NotConsumed)
+}
+
 /// helper fn to simplify delegating command
 /// gathering down into child components
 /// see `event_pump`,`accessors`
@@ -168,6 +218,18 @@ pub trait DrawableComponent {
     ) -> Result<()>;
 }
 
+/// a component that can be put on the popup stack,
+/// drawn by the app on top of everything else
+pub trait Popup: Component {
+    fn draw_popup(&self, f: &mut Frame<UiBackend>, rect: Rect) -> Result<()>;
+}
+
+impl<T: Component + DrawableComponent> Popup for T {
+    fn draw_popup(&self, f: &mut Frame<UiBackend>, rect: Rect) -> Result<()> {
+        self.draw(f, rect)
+    }
+}
+
 ///
 #[derive(PartialEq)]
 pub enum EventState {
@@ -203,12 +265,30 @@ pub trait Component {
     ///
     fn event(&mut self, ev: Event) -> Result<EventState>;
 
+    /// runs `action` as its key would, for the command palette,
+    /// only actions of commands the component lists need handling
+    fn run_action(&mut self, _action: ActionId) -> Result<EventState> {
+        Ok(EventState::NotConsumed)
+    }
+
+    /// what the external editor returned for the text this component
+    /// queued with `InternalEvent::OpenExternalEditor`,
+    /// `None` if the editor failed, others leave it alone
+    fn edited(&mut self, _text: Option<&str>) -> Result<EventState> {
+        Ok(EventState::NotConsumed)
+    }
+
     ///
     fn focused(&self) -> bool {
         false
     }
     /// focus/unfocus this component depending on param
     fn focus(&mut self, _focus: bool) {}
+    /// a modal popup on top of the popup stack takes all input
+    /// and dims everything below it
+    fn is_modal(&self) -> bool {
+        true
+    }
     ///
     fn is_visible(&self) -> bool {
         true
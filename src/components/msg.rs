@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    Frame,
+    layout::Rect,
+    text::Text,
+    widgets::Clear,
+};
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    strings, ui,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    popup_paragraph, visibility_blocking, CommandBlocking, CommandInfo,
+    Component, DrawableComponent, EventState,
+};
+
+/// how serious a message is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MsgKind {
+    Error,
+    Warning,
+    Info,
+    Success,
+}
+
+/// shows messages one at a time, the ones that come in
+/// while a message is shown wait in line behind it
+pub struct MsgComponent {
+    messages: VecDeque<(MsgKind, String)>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for MsgComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if let Some((kind, msg)) = self.messages.front() {
+            const SIZE: (u16, u16) = (60, 12);
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+
+            let title = strings::msg_title(
+                &self.key_config,
+                *kind,
+                self.messages.len() - 1,
+            );
+            let style = match kind {
+                MsgKind::Error => self.theme.text_danger(),
+                MsgKind::Warning | MsgKind::Info | MsgKind::Success => {
+                    self.theme.text(true, false)
+                }
+            };
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                popup_paragraph(
+                    &title,
+                    Text::styled(msg.as_str(), style),
+                    &self.theme,
+                    true,
+                ),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for MsgComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::msg_dismiss(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if self.is_visible() {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.borrow().popup.exit_popup
+                    || e == self.key_config.borrow().popup.select
+                {
+                    self.messages.pop_front();
+                } else if e == self.key_config.borrow().global.exit {
+                    return Ok(EventState::NotConsumed);
+                }
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.is_visible() {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::Popup, "exit_popup") => self.hide(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        !self.messages.is_empty()
+    }
+
+    fn hide(&mut self) {
+        self.messages.pop_front();
+    }
+}
+
+impl MsgComponent {
+    pub fn new(theme: SharedTheme, key_config: SharedKeyConfig) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// queues `msg`, unless the very same message is already waiting
+    pub fn show(&mut self, kind: MsgKind, msg: String) {
+        if !self.messages.iter().any(|(k, m)| *k == kind && *m == msg) {
+            self.messages.push_back((kind, msg));
+        }
+    }
+
+    pub fn show_error(&mut self, msg: String) {
+        self.show(MsgKind::Error, msg);
+    }
+}
@@ -0,0 +1,330 @@
+use std::{borrow::Cow, cmp, convert::TryFrom};
+
+use anyhow::Result;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, Borders, BorderType, Clear, Paragraph},
+};
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    strings, ui,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    CommandBlocking, CommandInfo, CommandText, Component,
+    DrawableComponent, EventState, input_char, visibility_blocking,
+};
+
+/// scores how well `query` matches `text` as a case insensitive
+/// subsequence, consecutive and early matches score higher
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last: Option<usize> = None;
+
+    for q in query.to_lowercase().chars() {
+        let found = text[pos..].iter().position(|c| *c == q)? + pos;
+        score += match last {
+            Some(l) if l + 1 == found => 10,
+            _ => 1,
+        };
+        if found == 0 {
+            score += 5;
+        }
+        last = Some(found);
+        pos = found + 1;
+    }
+
+    Some(score * 100 - i64::try_from(text.len()).unwrap_or(0))
+}
+
+/// searches the commands available in the current context
+/// and hands the chosen action back to the app to be run
+pub struct CommandPaletteComponent {
+    visible: bool,
+    query: String,
+    commands: Vec<CommandText>,
+    filtered: Vec<usize>,
+    selection: u16,
+    action: Option<ActionId>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CommandPaletteComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (60, 16);
+            let scroll_threshold = SIZE.1 / 3;
+            let scroll =
+                self.selection.saturating_sub(scroll_threshold);
+
+            let area =
+                ui::centered_rect_absolute(SIZE.0, SIZE.1, f.size());
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Block::default()
+                    .title(strings::palette_title(&self.key_config))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+                area,
+            );
+
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints(
+                    [Constraint::Length(2), Constraint::Min(1)]
+                        .as_ref(),
+                )
+                .split(area);
+
+            f.render_widget(
+                Paragraph::new(Spans::from(format!("> {}", self.query)))
+                    .block(Block::default().borders(Borders::BOTTOM))
+                    .alignment(Alignment::Left),
+                chunks[0],
+            );
+
+            f.render_widget(
+                Paragraph::new(self.get_text())
+                    .scroll((scroll, 0))
+                    .alignment(Alignment::Left),
+                chunks[1],
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CommandPaletteComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible && !force_all {
+            out.clear();
+        }
+
+        if self.visible {
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            let run = CommandInfo::new(
+                strings::commands::palette_run(&self.key_config),
+                true,
+                true,
+            );
+            out.push(if self.filtered.is_empty() {
+                run.disabled_because(strings::DISABLED_NO_MATCH)
+            } else {
+                run
+            });
+            out.push(CommandInfo::new(
+                strings::commands::input_cancel(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        if !self.visible || force_all {
+            out.push(
+                CommandInfo::new(
+                    strings::commands::palette_open(&self.key_config),
+                    true,
+                    true,
+                )
+                .order(98),
+            );
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let keys = self.key_config.borrow().text_input.clone();
+                if e == keys.cancel {
+                    self.hide();
+                } else if e == keys.confirm {
+                    self.action = self.selected().and_then(|c| c.action);
+                    self.hide();
+                } else if e == self.key_config.borrow().popup.scroll_down {
+                    self.move_selection(true);
+                } else if e == self.key_config.borrow().popup.scroll_up {
+                    self.move_selection(false);
+                } else if e == keys.delete_back {
+                    self.query.pop();
+                    self.refilter();
+                } else if let Some(c) = input_char(e) {
+                    self.query.push(c);
+                    self.refilter();
+                }
+            }
+
+            Ok(EventState::Consumed)
+        } else {
+            Ok(EventState::NotConsumed)
+        }
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::TextInput, "cancel") => self.hide(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.action = None;
+        self.query.clear();
+        self.refilter();
+
+        Ok(())
+    }
+}
+
+impl CommandPaletteComponent {
+    pub fn new(
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            visible: false,
+            query: String::new(),
+            commands: Vec::new(),
+            filtered: Vec::new(),
+            selection: 0,
+            action: None,
+            theme,
+            key_config,
+        }
+    }
+
+    /// shows the palette offering `commands`,
+    /// only those with an action can be run from here
+    pub fn open(&mut self, commands: Vec<CommandText>) -> Result<()> {
+        self.commands = commands
+            .into_iter()
+            .filter(|c| c.action.is_some())
+            .collect();
+        self.commands.sort_by_key(|c| c.action);
+        self.commands.dedup_by(|a, b| a.action == b.action);
+        self.commands.sort_by(|a, b| a.name.cmp(&b.name));
+        self.show()
+    }
+
+    /// the action the user picked, once
+    pub fn take_action(&mut self) -> Option<ActionId> {
+        self.action.take()
+    }
+
+    fn selected(&self) -> Option<&CommandText> {
+        self.filtered
+            .get(self.selection as usize)
+            .map(|i| &self.commands[*i])
+    }
+
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                let name = fuzzy_score(&self.query, &c.name);
+                let desc = fuzzy_score(&self.query, c.desc)
+                    .map(|s| s / 2);
+                cmp::max(name, desc).map(|s| (s, i))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        self.filtered = scored.into_iter().map(|(_, i)| i).collect();
+        self.selection = 0;
+    }
+
+    fn move_selection(&mut self, inc: bool) {
+        let new_selection = if inc {
+            self.selection.saturating_add(1)
+        } else {
+            self.selection.saturating_sub(1)
+        };
+
+        if let Ok(max) =
+            u16::try_from(self.filtered.len().saturating_sub(1))
+        {
+            self.selection = cmp::min(new_selection, max);
+        }
+    }
+
+    fn get_text(&self) -> Vec<Spans<'_>> {
+        self.filtered
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let c = &self.commands[*c];
+                let is_selected = self.selection as usize == i;
+                Spans::from(vec![
+                    Span::styled(
+                        Cow::from(format!(
+                            "{}{:<24}",
+                            if is_selected { ">" } else { " " },
+                            c.name
+                        )),
+                        self.theme.text(true, is_selected),
+                    ),
+                    Span::styled(
+                        Cow::from(format!("{} - {}", c.group, c.desc)),
+                        self.theme.text(false, is_selected),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_score;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("hlp", "Help [h]").is_some());
+        assert!(fuzzy_score("xyz", "Help [h]").is_none());
+        assert!(fuzzy_score("", "Quit").is_some());
+        assert!(
+            fuzzy_score("qu", "Quit [q]") > fuzzy_score("qu", "Request")
+        );
+    }
+}
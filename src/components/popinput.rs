@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::Event;
 use log::info;
 use tui::backend::Backend;
 use tui::Frame;
@@ -8,8 +8,8 @@ use tui::style::{Color, Style};
 use tui::widgets::{Block, Borders, Clear, Paragraph};
 use unicode_width::UnicodeWidthStr;
 
-use crate::{components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState, visibility_blocking}, strings, ui};
-use crate::keys::SharedKeyConfig;
+use crate::{components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState, input_char, visibility_blocking}, queue::{InternalEvent, Queue}, strings, ui};
+use crate::keys::{ActionId, KeyContext, SharedKeyConfig};
 
 pub enum InputMode {
     Normal,
@@ -24,6 +24,9 @@ pub struct PopInputComponent {
     input_position: usize,
     cursor_position: usize,
     message: String,
+    /// waits for the external editor
+    editing: bool,
+    queue: Queue,
     key_config: SharedKeyConfig,
 }
 
@@ -68,21 +71,21 @@ impl DrawableComponent for PopInputComponent {
 impl Component for PopInputComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>, force_all: bool) -> CommandBlocking {
         if self.is_visible() || force_all {
-            // let mut normal = true;
-            // match self.input_mode {
-            //     InputMode::Editing => {
-            //         normal = false;
-            //     }
-            //     _ => {}
-            // }
-            // out.push(
-            //     CommandInfo::new(
-            //         strings::commands::search_input_enable(&self.key_config),
-            //         true,
-            //         normal,
-            //     )
-            //         .order(1)
-            // );
+            out.push(CommandInfo::new(
+                strings::commands::input_confirm(&self.key_config),
+                true,
+                self.is_visible(),
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_open_editor(&self.key_config),
+                true,
+                self.is_visible(),
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_cancel(&self.key_config),
+                true,
+                self.is_visible(),
+            ));
         }
         visibility_blocking(self)
     }
@@ -90,46 +93,36 @@ impl Component for PopInputComponent {
     fn event(&mut self, ev: Event) -> anyhow::Result<EventState> {
         if self.is_visible() {
             if let Event::Key(key) = ev {
-                match key.code {
-                    KeyCode::Enter => {
-                        // self.message.push(self.input.drain(..).collect());
-                        self.message = self.input.clone();
-                        self.clear();
+                let keys = self.key_config.borrow().text_input.clone();
+                if key == keys.confirm {
+                    self.submit();
+                } else if key == keys.delete_back {
+                    if self.cursor_left() {
+                        self.input.remove(self.input_position);
+                    };
+                } else if key == keys.delete_forward {
+                    if self.input.len() > self.input_position {
+                        self.input.remove(self.input_position);
                     }
-                    KeyCode::Char(c) => {
-                        if self.input.width() == self.cursor_position {
-                            self.input.push(c);
-                            self.input_position = self.input.len();
-                            self.cursor_position = self.input.width();
-                        }
-
-                        if self.input.width() > self.cursor_position {
-                            self.input.insert(self.input_position, c);
-                            self.cursor_right();
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        if self.cursor_left() {
-                            self.input.remove(self.input_position);
-                        };
-                    }
-
-                    KeyCode::Delete => {
-                        if self.input.len() > self.input_position {
-                            self.input.remove(self.input_position);
-                        }
-                    }
-                    KeyCode::Left => {
-                        self.cursor_left();
-                    }
-                    KeyCode::Right => {
+                } else if key == keys.cursor_left {
+                    self.cursor_left();
+                } else if key == keys.cursor_right {
+                    self.cursor_right();
+                } else if key == keys.cancel {
+                    self.hide();
+                } else if key == keys.open_editor {
+                    self.open_editor();
+                } else if let Some(c) = input_char(key) {
+                    if self.input.width() == self.cursor_position {
+                        self.input.push(c);
+                        self.input_position = self.input.len();
+                        self.cursor_position = self.input.width();
+                    } else {
+                        self.input.insert(self.input_position, c);
                         self.cursor_right();
                     }
-                    KeyCode::Esc => {
-                        self.clear();
-                        self.hide();
-                    }
-                    _ => { return Ok(EventState::NotConsumed); }
+                } else {
+                    return Ok(EventState::NotConsumed);
                 }
                 return Ok(EventState::Consumed);
             }
@@ -137,6 +130,31 @@ impl Component for PopInputComponent {
         Ok(EventState::NotConsumed)
     }
 
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.is_visible() {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::TextInput, "confirm") => self.submit(),
+            (KeyContext::TextInput, "cancel") => self.hide(),
+            (KeyContext::TextInput, "open_editor") => self.open_editor(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    /// only the first line of what the editor returns is kept
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        if !self.editing {
+            return Ok(EventState::NotConsumed);
+        }
+        self.editing = false;
+        if let Some(text) = text {
+            self.set_input(text.lines().next().unwrap_or_default());
+        }
+        Ok(EventState::Consumed)
+    }
+
     fn is_visible(&self) -> bool {
         self.visible
     }
@@ -155,6 +173,7 @@ impl Component for PopInputComponent {
 
 impl PopInputComponent {
     pub fn new(
+        queue: &Queue,
         key_config: SharedKeyConfig,
     ) -> Self {
         Self {
@@ -166,6 +185,8 @@ impl PopInputComponent {
             cursor_position: 0,
             // message: vec![],
             message: "".to_string(),
+            editing: false,
+            queue: queue.clone(),
             key_config,
         }
     }
@@ -184,12 +205,30 @@ impl PopInputComponent {
     //     return &self.input_mode;
     // }
 
+    fn submit(&mut self) {
+        self.message = self.input.clone();
+        self.clear();
+    }
+
+    fn open_editor(&mut self) {
+        self.queue
+            .push(InternalEvent::OpenExternalEditor(self.input.clone()));
+        self.editing = true;
+    }
+
     fn clear(&mut self) {
         self.input.clear();
         self.cursor_position = 0;
         self.input_position = 0;
     }
 
+    /// replaces the input, the cursor goes behind it
+    fn set_input(&mut self, text: &str) {
+        self.input = text.to_string();
+        self.input_position = self.input.len();
+        self.cursor_position = self.input.width();
+    }
+
     fn cursor_left(&mut self) -> bool {
         if self.cursor_position > 0 {
             let mut index = self.input_position - 1;
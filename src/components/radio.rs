@@ -0,0 +1,175 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{keys::SharedKeyConfig, strings, ui::style::SharedTheme};
+
+use super::{
+    clicked, dialog_paragraph, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, EventState,
+};
+
+/// columns between two options
+const GAP: &str = "  ";
+
+/// options side by side of which exactly one is selected
+pub struct RadioGroupComponent {
+    label: String,
+    options: Vec<String>,
+    selected: usize,
+    focused: bool,
+    area: Cell<Rect>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl RadioGroupComponent {
+    pub fn new(
+        label: &str,
+        options: Vec<String>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            label: label.to_string(),
+            options,
+            selected: 0,
+            focused: false,
+            area: Cell::new(Rect::default()),
+            theme,
+            key_config,
+        }
+    }
+
+    /// the selected option, `None` without options
+    pub fn selected(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+
+    /// selects the option equal to `option`, if there is one
+    pub fn select(&mut self, option: &str) {
+        if let Some(index) = self.options.iter().position(|o| o == option) {
+            self.selected = index;
+        }
+    }
+
+    fn previous(&mut self) {
+        self.selected = self
+            .selected
+            .checked_sub(1)
+            .unwrap_or(self.options.len() - 1);
+    }
+
+    fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.options.len();
+    }
+
+    fn marker(&self, index: usize) -> &'static str {
+        if index == self.selected {
+            "(•) "
+        } else {
+            "( ) "
+        }
+    }
+
+    /// the option drawn at `column`, the gaps belong to no option
+    fn option_at(&self, column: u16) -> Option<usize> {
+        let mut x = self.area.get().x + 1;
+        for (index, option) in self.options.iter().enumerate() {
+            let width = (self.marker(index).width() + option.width()) as u16;
+            if column >= x && column < x + width {
+                return Some(index);
+            }
+            x += width + GAP.len() as u16;
+        }
+        None
+    }
+}
+
+impl DrawableComponent for RadioGroupComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) -> Result<()> {
+        let mut spans = Vec::new();
+        for (index, option) in self.options.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw(GAP));
+            }
+            let on = index == self.selected;
+            spans.push(Span::styled(self.marker(index), self.theme.option(on)));
+            spans.push(Span::styled(
+                option.as_str(),
+                self.theme.text(true, on && self.focused),
+            ));
+        }
+
+        f.render_widget(
+            dialog_paragraph(
+                &self.label,
+                Spans::from(spans).into(),
+                &self.theme,
+                self.focused,
+            ),
+            rect,
+        );
+        self.area.set(rect);
+
+        Ok(())
+    }
+}
+
+impl Component for RadioGroupComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.focused || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::form_select(&self.key_config),
+                self.options.len() > 1,
+                true,
+            ));
+        }
+        CommandBlocking::PassingOn
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if self.options.is_empty() {
+            return Ok(EventState::NotConsumed);
+        }
+
+        if let Event::Key(key) = ev {
+            let keys = self.key_config.borrow().text_input.clone();
+            if self.focused && key == keys.cursor_left {
+                self.previous();
+                return Ok(EventState::Consumed);
+            }
+            if self.focused && key == keys.cursor_right {
+                self.next();
+                return Ok(EventState::Consumed);
+            }
+        } else if let Some((column, _)) = clicked(ev, self.area.get()) {
+            if let Some(index) = self.option_at(column) {
+                self.selected = index;
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self, focus: bool) {
+        self.focused = focus;
+    }
+}
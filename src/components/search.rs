@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::Event;
 use log::info;
 use tui::backend::Backend;
 use tui::Frame;
@@ -9,10 +9,11 @@ use tui::widgets::{Block, Borders, Paragraph};
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState, visibility_blocking},
+    components::{CommandBlocking, CommandInfo, Component, DrawableComponent, EventState, input_char, visibility_blocking},
+    queue::{InternalEvent, Queue},
     strings,
 };
-use crate::keys::SharedKeyConfig;
+use crate::keys::{ActionId, KeyContext, SharedKeyConfig};
 
 pub enum InputMode {
     Normal,
@@ -27,6 +28,9 @@ pub struct SearchComponent {
     input_position: usize,
     cursor_position: usize,
     message: String,
+    /// waits for the external editor
+    editing: bool,
+    queue: Queue,
     key_config: SharedKeyConfig,
 }
 
@@ -90,6 +94,21 @@ impl Component for SearchComponent {
                 )
                     .order(1)
             );
+            out.push(CommandInfo::new(
+                strings::commands::input_confirm(&self.key_config),
+                true,
+                !normal,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_open_editor(&self.key_config),
+                true,
+                !normal,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_cancel(&self.key_config),
+                true,
+                !normal,
+            ));
         }
         visibility_blocking(self)
     }
@@ -97,73 +116,95 @@ impl Component for SearchComponent {
     fn event(&mut self, ev: Event) -> anyhow::Result<EventState> {
         if self.is_visible() {
             if let Event::Key(key) = ev {
+                let keys = self.key_config.borrow().text_input.clone();
                 match self.input_mode {
                     InputMode::Editing => {
-                        match key.code {
-
-                            // KeyCode::Char('\n') => {
-                            KeyCode::Enter => {
-                                // self.message.push(self.input.drain(..).collect());
-                                self.message = self.input.clone();
-                                self.clear();
-                            }
-                            KeyCode::Char(c) => {
-                                if self.input.width() == self.cursor_position {
-                                    self.input.push(c);
-                                    self.input_position = self.input.len();
-                                    self.cursor_position = self.input.width();
-                                }
-
-                                if self.input.width() > self.cursor_position {
-                                    self.input.insert(self.input_position, c);
-                                    self.cursor_right();
-                                }
-                            }
-                            KeyCode::Backspace => {
-                                if self.cursor_left() {
-                                    self.input.remove(self.input_position);
-                                };
-                            }
-
-                            KeyCode::Delete => {
-                                if self.input.len() > self.input_position {
-                                    self.input.remove(self.input_position);
-                                }
+                        if key == keys.confirm {
+                            self.submit();
+                        } else if key == keys.delete_back {
+                            if self.cursor_left() {
+                                self.input.remove(self.input_position);
+                            };
+                        } else if key == keys.delete_forward {
+                            if self.input.len() > self.input_position {
+                                self.input.remove(self.input_position);
                             }
-                            KeyCode::Left => {
-                                self.cursor_left();
-                            }
-                            KeyCode::Right => {
+                        } else if key == keys.cursor_left {
+                            self.cursor_left();
+                        } else if key == keys.cursor_right {
+                            self.cursor_right();
+                        } else if key == keys.cancel {
+                            self.cancel();
+                        } else if key == keys.open_editor {
+                            self.open_editor();
+                        } else if let Some(c) = input_char(key) {
+                            if self.input.width() == self.cursor_position {
+                                self.input.push(c);
+                                self.input_position = self.input.len();
+                                self.cursor_position = self.input.width();
+                            } else {
+                                self.input.insert(self.input_position, c);
                                 self.cursor_right();
                             }
-                            KeyCode::Esc => {
-                                self.clear();
-                                self.input_mode = InputMode::Normal;
-                            }
-                            _ => { return Ok(EventState::NotConsumed); }
+                        } else {
+                            return Ok(EventState::NotConsumed);
                         }
                         return Ok(EventState::Consumed);
                     }
                     InputMode::Normal => {
-                        match key.code {
-                            KeyCode::Char('s') => {
-                                self.input_mode = InputMode::Editing;
-                                return Ok(EventState::Consumed);
-                            }
-                            _ => {}
+                        if key == self.key_config.borrow().tab.search {
+                            self.input_mode = InputMode::Editing;
+                            return Ok(EventState::Consumed);
                         }
                     }
-                    _ => {}
                 }
             }
         }
         Ok(EventState::NotConsumed)
     }
 
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.is_visible() {
+            return Ok(EventState::NotConsumed);
+        }
+        match (&self.input_mode, action.context, action.action) {
+            (InputMode::Editing, KeyContext::TextInput, "confirm") => {
+                self.submit();
+            }
+            (InputMode::Editing, KeyContext::TextInput, "cancel") => {
+                self.cancel();
+            }
+            (InputMode::Editing, KeyContext::TextInput, "open_editor") => {
+                self.open_editor();
+            }
+            (InputMode::Normal, KeyContext::Tab, "search") => {
+                self.input_mode = InputMode::Editing;
+            }
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    /// only the first line of what the editor returns is kept
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        if !self.editing {
+            return Ok(EventState::NotConsumed);
+        }
+        self.editing = false;
+        if let Some(text) = text {
+            self.set_input(text.lines().next().unwrap_or_default());
+        }
+        Ok(EventState::Consumed)
+    }
+
     fn is_visible(&self) -> bool {
         self.visible
     }
 
+    fn focused(&self) -> bool {
+        matches!(self.input_mode, InputMode::Editing)
+    }
+
     fn hide(&mut self) {
         self.clear();
         self.visible = false;
@@ -178,6 +219,7 @@ impl Component for SearchComponent {
 
 impl SearchComponent {
     pub fn new(
+        queue: &Queue,
         key_config: SharedKeyConfig,
     ) -> Self {
         Self {
@@ -189,6 +231,8 @@ impl SearchComponent {
             cursor_position: 0,
             // message: vec![],
             message: "".to_string(),
+            editing: false,
+            queue: queue.clone(),
             key_config,
         }
     }
@@ -207,12 +251,45 @@ impl SearchComponent {
         return &self.input_mode;
     }
 
+    /// starts or cancels editing as if the keys had been pressed
+    pub fn set_editing(&mut self, editing: bool) {
+        if editing {
+            self.input_mode = InputMode::Editing;
+        } else {
+            self.clear();
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    fn submit(&mut self) {
+        self.message = self.input.clone();
+        self.clear();
+    }
+
+    fn cancel(&mut self) {
+        self.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn open_editor(&mut self) {
+        self.queue
+            .push(InternalEvent::OpenExternalEditor(self.input.clone()));
+        self.editing = true;
+    }
+
     fn clear(&mut self) {
         self.input.clear();
         self.cursor_position = 0;
         self.input_position = 0;
     }
 
+    /// replaces the input, the cursor goes behind it
+    fn set_input(&mut self, text: &str) {
+        self.input = text.to_string();
+        self.input_position = self.input.len();
+        self.cursor_position = self.input.width();
+    }
+
     fn cursor_left(&mut self) -> bool {
         if self.cursor_position > 0 {
             let mut index = self.input_position - 1;
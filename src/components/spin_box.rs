@@ -0,0 +1,166 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    Frame,
+};
+
+use crate::{keys::SharedKeyConfig, strings, ui::style::SharedTheme};
+
+use super::{
+    clicked, dialog_paragraph, form::Input, input_char, CommandBlocking,
+    CommandInfo, Component, DrawableComponent, EventState,
+};
+
+/// a whole number that is typed or stepped through,
+/// stepping stays in `min..=max`, typing is checked by the caller
+pub struct SpinBoxComponent {
+    label: String,
+    input: Input,
+    min: i64,
+    max: i64,
+    step: i64,
+    focused: bool,
+    area: Cell<Rect>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl SpinBoxComponent {
+    pub fn new(
+        label: &str,
+        min: i64,
+        max: i64,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            label: label.to_string(),
+            input: Input::default(),
+            min,
+            max: max.max(min),
+            step: 1,
+            focused: false,
+            area: Cell::new(Rect::default()),
+            theme,
+            key_config,
+        }
+    }
+
+    pub fn step(mut self, step: i64) -> Self {
+        self.step = step.max(1);
+        self
+    }
+
+    /// `None` while empty or not a number
+    pub fn value(&self) -> Option<i64> {
+        self.input.text().parse().ok()
+    }
+
+    pub fn text(&self) -> &str {
+        self.input.text()
+    }
+
+    pub fn set_value(&mut self, value: Option<i64>) {
+        match value {
+            Some(value) => self.input.set_text(&value.to_string()),
+            None => self.input.clear(),
+        }
+    }
+
+    /// moves `steps` steps, an empty box starts at the value nearest to 0
+    fn spin(&mut self, steps: i64) {
+        let value = match self.value() {
+            Some(value) => value.saturating_add(steps.saturating_mul(self.step)),
+            None => 0,
+        };
+        self.set_value(Some(value.clamp(self.min, self.max)));
+    }
+}
+
+impl DrawableComponent for SpinBoxComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) -> Result<()> {
+        let text = self.input.text();
+        let in_range = self
+            .value()
+            .map_or(text.is_empty(), |v| v >= self.min && v <= self.max);
+        let range = format!("  {}..{}", self.min, self.max);
+        f.render_widget(
+            dialog_paragraph(
+                &self.label,
+                Spans::from(vec![
+                    Span::styled(text, self.theme.option(in_range)),
+                    Span::styled(range, self.theme.text(false, false)),
+                ])
+                .into(),
+                &self.theme,
+                self.focused,
+            ),
+            rect,
+        );
+        self.area.set(rect);
+
+        if self.focused {
+            let (_, column) = self.input.cursor();
+            f.set_cursor(
+                (rect.x + 1 + column).min(rect.right().saturating_sub(2)),
+                rect.y + 1,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for SpinBoxComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.focused || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::spin_step(&self.key_config),
+                true,
+                true,
+            ));
+        }
+        CommandBlocking::PassingOn
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if let Event::Key(key) = ev {
+            if !self.focused {
+                return Ok(EventState::NotConsumed);
+            }
+            let keys = self.key_config.borrow().text_input.clone();
+            let taken = if key == keys.cursor_left {
+                self.spin(-1);
+                true
+            } else if key == keys.cursor_right {
+                self.spin(1);
+                true
+            } else {
+                match input_char(key) {
+                    Some(c) if !c.is_ascii_digit() && c != '-' => false,
+                    _ => self.input.event(key, &keys),
+                }
+            };
+            return Ok(taken.into());
+        }
+
+        Ok(clicked(ev, self.area.get()).is_some().into())
+    }
+
+    fn focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self, focus: bool) {
+        self.focused = focus;
+    }
+}
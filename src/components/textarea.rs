@@ -0,0 +1,734 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    queue::{InternalEvent, Queue},
+    settings::SharedSettings,
+    strings,
+    ui::{self, style::SharedTheme},
+};
+
+use super::{
+    input_char, visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, EventState,
+};
+
+/// changes undo can take back
+const UNDO_LIMIT: usize = 100;
+
+/// options of multi line text inputs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct TextAreaConfig {
+    /// length of the first line past which the title warns, `0` for never
+    pub first_line_max: usize,
+}
+
+impl Default for TextAreaConfig {
+    fn default() -> Self {
+        Self { first_line_max: 50 }
+    }
+}
+
+/// text and cursor as undo restores them
+struct Snapshot {
+    text: String,
+    cursor: usize,
+}
+
+/// a row as drawn, the byte range of the text it shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Row {
+    start: usize,
+    end: usize,
+}
+
+/// splits `text` into rows of at most `width` columns,
+/// a row that is too long breaks after its last space if it has one
+fn wrap(text: &str, width: usize) -> Vec<Row> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut line_start = 0;
+    for line in text.split('\n') {
+        let mut start = line_start;
+        let mut columns = 0;
+        let mut after_space = None;
+        for (i, c) in line.char_indices() {
+            let pos = line_start + i;
+            let w = c.width().unwrap_or(0);
+            if columns + w > width && pos > start {
+                let end = after_space.filter(|s| *s > start).unwrap_or(pos);
+                rows.push(Row { start, end });
+                columns = text[end..pos].width();
+                start = end;
+                after_space = None;
+            }
+            columns += w;
+            if c == ' ' {
+                after_space = Some(pos + 1);
+            }
+        }
+        rows.push(Row {
+            start,
+            end: line_start + line.len(),
+        });
+        line_start += line.len() + 1;
+    }
+    rows
+}
+
+/// the row the cursor at `pos` is drawn in
+fn row_of(rows: &[Row], pos: usize) -> usize {
+    rows.iter().rposition(|r| r.start <= pos).unwrap_or(0)
+}
+
+/// a multi line text popup with selection and undo,
+/// the title counts characters and lines
+pub struct TextAreaComponent {
+    title: String,
+    placeholder: String,
+    text: String,
+    /// byte position in `text`
+    cursor: usize,
+    /// the end of the selection the cursor is not at
+    anchor: Option<usize>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// typing a word is undone as a whole
+    typing: bool,
+    visible: bool,
+    submitted: Option<String>,
+    /// waits for the external editor
+    editing: bool,
+    /// columns and first row of the last draw
+    width: Cell<u16>,
+    scroll: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+    settings: SharedSettings,
+}
+
+impl TextAreaComponent {
+    pub fn new(
+        title: &str,
+        placeholder: &str,
+        queue: &Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        settings: SharedSettings,
+    ) -> Self {
+        Self {
+            title: title.to_string(),
+            placeholder: placeholder.to_string(),
+            text: String::new(),
+            cursor: 0,
+            anchor: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing: false,
+            visible: false,
+            submitted: None,
+            editing: false,
+            width: Cell::new(u16::MAX),
+            scroll: Cell::new(0),
+            queue: queue.clone(),
+            theme,
+            key_config,
+            settings,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// replaces the text, which cannot be undone
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.cursor = self.text.len();
+        self.anchor = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = false;
+    }
+
+    /// the text of the last submit
+    pub fn take_submitted(&mut self) -> Option<String> {
+        self.submitted.take()
+    }
+
+    /// start and end of a selection that is not empty
+    fn selection(&self) -> Option<(usize, usize)> {
+        self.anchor
+            .filter(|a| *a != self.cursor)
+            .map(|a| (a.min(self.cursor), a.max(self.cursor)))
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        wrap(&self.text, usize::from(self.width.get()))
+    }
+
+    /// remembers the text for undo before it changes
+    fn change(&mut self, typing: bool) {
+        if !(typing && self.typing) {
+            self.undo.push(Snapshot {
+                text: self.text.clone(),
+                cursor: self.cursor,
+            });
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.typing = typing;
+        self.redo.clear();
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                self.text.replace_range(start..end, "");
+                self.cursor = start;
+                self.anchor = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// replaces the selection, if any, with `s`,
+    /// typing over a selection starts a new undo step
+    fn insert(&mut self, s: &str, typing: bool) {
+        self.change(typing && self.selection().is_none());
+        self.typing = typing;
+        self.delete_selection();
+        self.anchor = None;
+        self.text.insert_str(self.cursor, s);
+        self.cursor += s.len();
+    }
+
+    fn delete(&mut self, forward: bool) {
+        if self.selection().is_some() {
+            self.change(false);
+            self.delete_selection();
+            return;
+        }
+        let range = if forward {
+            self.cursor..self.next(self.cursor)
+        } else {
+            self.prev(self.cursor)..self.cursor
+        };
+        if !range.is_empty() {
+            self.change(false);
+            self.cursor = range.start;
+            self.text.replace_range(range, "");
+        }
+        self.anchor = None;
+    }
+
+    fn prev(&self, pos: usize) -> usize {
+        self.text[..pos]
+            .chars()
+            .next_back()
+            .map_or(pos, |c| pos - c.len_utf8())
+    }
+
+    fn next(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .chars()
+            .next()
+            .map_or(pos, |c| pos + c.len_utf8())
+    }
+
+    /// last position that is drawn in row `index`, a row broken by
+    /// wrapping ends before the position the next row starts at
+    fn row_end(&self, rows: &[Row], index: usize) -> usize {
+        let row = rows[index];
+        match rows.get(index + 1) {
+            Some(next) if next.start == row.end && row.end > row.start => {
+                self.prev(row.end)
+            }
+            _ => row.end,
+        }
+    }
+
+    /// the position `delta` rows away in about the same column
+    fn vertical(&self, delta: isize) -> usize {
+        let rows = self.rows();
+        let current = row_of(&rows, self.cursor);
+        let target = current as isize + delta;
+        if target < 0 {
+            return 0;
+        }
+        if target as usize >= rows.len() {
+            return self.text.len();
+        }
+
+        let column = self.text[rows[current].start..self.cursor].width();
+        let target = target as usize;
+        let end = self.row_end(&rows, target);
+        let mut pos = rows[target].start;
+        let mut width = 0;
+        for c in self.text[pos..end].chars() {
+            width += c.width().unwrap_or(0);
+            if width > column {
+                break;
+            }
+            pos += c.len_utf8();
+        }
+        pos
+    }
+
+    fn move_to(&mut self, pos: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = pos;
+        self.typing = false;
+    }
+
+    /// the text waits for `take_submitted`
+    fn submit(&mut self) {
+        self.submitted = Some(self.text().to_string());
+        self.hide();
+    }
+
+    fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.undo.pop() {
+            let current = self.restore(snapshot);
+            self.redo.push(current);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.redo.pop() {
+            let current = self.restore(snapshot);
+            self.undo.push(current);
+        }
+    }
+
+    /// puts `snapshot` in place and returns what it replaced
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = Snapshot {
+            text: std::mem::replace(&mut self.text, snapshot.text),
+            cursor: self.cursor,
+        };
+        self.cursor = snapshot.cursor;
+        self.anchor = None;
+        self.typing = false;
+        current
+    }
+
+    /// hands the text to the external editor with a hint below it
+    fn open_editor(&mut self) {
+        let text = format!(
+            "{}\n{}",
+            self.text,
+            strings::commit_editor_msg(&self.key_config)
+        );
+        self.queue.push(InternalEvent::OpenExternalEditor(text));
+        self.editing = true;
+    }
+
+    /// where the first line grows past the configured length
+    fn first_line_overflow(&self) -> Option<(usize, usize)> {
+        let max = self.settings.text_area.first_line_max;
+        let first = self.text.split('\n').next().unwrap_or_default();
+        match first.char_indices().nth(max) {
+            Some((start, _)) if max > 0 => Some((start, first.len())),
+            _ => None,
+        }
+    }
+
+    fn style_at(&self, pos: usize) -> Style {
+        match (self.selection(), self.first_line_overflow()) {
+            (Some((start, end)), _) if pos >= start && pos < end => {
+                self.theme.text(true, true)
+            }
+            (_, Some((start, end))) if pos >= start && pos < end => {
+                self.theme.text_danger()
+            }
+            _ => Style::default(),
+        }
+    }
+
+    /// the row split into runs of the same style
+    fn row_spans(&self, row: Row) -> Spans<'_> {
+        let mut spans: Vec<Span> = Vec::new();
+        let mut run_start = row.start;
+        let mut run_style = self.style_at(row.start);
+        for (i, _) in self.text[row.start..row.end].char_indices() {
+            let pos = row.start + i;
+            let style = self.style_at(pos);
+            if style != run_style {
+                spans.push(Span::styled(&self.text[run_start..pos], run_style));
+                run_start = pos;
+                run_style = style;
+            }
+        }
+        spans.push(Span::styled(&self.text[run_start..row.end], run_style));
+        Spans::from(spans)
+    }
+}
+
+impl DrawableComponent for TextAreaComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, _rect: Rect) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+
+        let area = ui::centered_rect_absolute(64, 14, f.size());
+        let counter = strings::text_area_counter(
+            &self.key_config,
+            self.text.chars().count(),
+            self.text.split('\n').count(),
+        );
+        let title = match self.first_line_overflow() {
+            Some(_) => Span::styled(
+                format!(
+                    "{} {} {}",
+                    self.title,
+                    counter,
+                    strings::commit_first_line_warning(
+                        self.text.split('\n').next().unwrap_or_default().chars().count()
+                    )
+                ),
+                self.theme.text_danger(),
+            ),
+            None => Span::styled(
+                format!("{} {}", self.title, counter),
+                self.theme.title(true),
+            ),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(self.theme.block(true));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+
+        // one column stays free for the cursor behind a full row
+        self.width.set(inner.width.saturating_sub(1));
+        let rows = self.rows();
+        let height = usize::from(inner.height).max(1);
+        let cursor_row = row_of(&rows, self.cursor);
+        if cursor_row < self.scroll.get() {
+            self.scroll.set(cursor_row);
+        } else if cursor_row >= self.scroll.get() + height {
+            self.scroll.set(cursor_row + 1 - height);
+        }
+        let scroll = self.scroll.get();
+
+        if self.text.is_empty() {
+            f.render_widget(
+                Paragraph::new(self.placeholder.as_str())
+                    .style(self.theme.text(false, false)),
+                inner,
+            );
+        } else {
+            let lines: Vec<Spans> = rows
+                .iter()
+                .skip(scroll)
+                .take(height)
+                .map(|row| self.row_spans(*row))
+                .collect();
+            f.render_widget(Paragraph::new(lines), inner);
+        }
+
+        let column = self.text[rows[cursor_row].start..self.cursor].width();
+        f.set_cursor(
+            inner.x + (column as u16).min(inner.width.saturating_sub(1)),
+            inner.y + (cursor_row - scroll) as u16,
+        );
+
+        Ok(())
+    }
+}
+
+impl Component for TextAreaComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::text_area_submit(&self.key_config),
+                true,
+                self.visible,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::text_area_undo(&self.key_config),
+                !self.undo.is_empty() || !self.redo.is_empty(),
+                self.visible,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::text_area_select_all(&self.key_config),
+                !self.text.is_empty(),
+                self.visible,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_open_editor(&self.key_config),
+                true,
+                self.visible,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::input_cancel(&self.key_config),
+                true,
+                self.visible,
+            ));
+        }
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+
+        if let Event::Key(key) = ev {
+            let keys = self.key_config.borrow().text_area.clone();
+            let input = self.key_config.borrow().text_input.clone();
+            if key == self.key_config.borrow().global.exit {
+                return Ok(EventState::NotConsumed);
+            }
+
+            if key == keys.submit {
+                self.submit();
+            } else if key == input.cancel {
+                self.hide();
+            } else if key == input.open_editor {
+                self.open_editor();
+            } else if key == keys.newline {
+                self.insert("\n", false);
+            } else if key == keys.undo {
+                self.undo();
+            } else if key == keys.redo {
+                self.redo();
+            } else if key == keys.select_all {
+                self.select_all();
+            } else if key == keys.cursor_up || key == keys.select_up {
+                let pos = self.vertical(-1);
+                self.move_to(pos, key == keys.select_up);
+            } else if key == keys.cursor_down || key == keys.select_down {
+                let pos = self.vertical(1);
+                self.move_to(pos, key == keys.select_down);
+            } else if key == input.cursor_left || key == keys.select_left {
+                let pos = self.prev(self.cursor);
+                self.move_to(pos, key == keys.select_left);
+            } else if key == input.cursor_right || key == keys.select_right {
+                let pos = self.next(self.cursor);
+                self.move_to(pos, key == keys.select_right);
+            } else if key == keys.line_start {
+                let rows = self.rows();
+                let pos = rows[row_of(&rows, self.cursor)].start;
+                self.move_to(pos, false);
+            } else if key == keys.line_end {
+                let rows = self.rows();
+                let pos = self.row_end(&rows, row_of(&rows, self.cursor));
+                self.move_to(pos, false);
+            } else if key == input.delete_back {
+                self.delete(false);
+            } else if key == input.delete_forward {
+                self.delete(true);
+            } else if let Some(c) = input_char(key) {
+                self.insert(&c.to_string(), !c.is_whitespace());
+            }
+            // nothing behind the editor gets keys while it is open
+            return Ok(EventState::Consumed);
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::TextArea, "submit") => self.submit(),
+            (KeyContext::TextArea, "undo") => self.undo(),
+            (KeyContext::TextArea, "select_all") => self.select_all(),
+            (KeyContext::TextInput, "cancel") => self.hide(),
+            (KeyContext::TextInput, "open_editor") => self.open_editor(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    /// lines starting with '#' do not come back, the hint among them
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        if !self.editing {
+            return Ok(EventState::NotConsumed);
+        }
+        self.editing = false;
+        if let Some(text) = text {
+            let text = text
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            self.change(false);
+            self.text = text.trim_end().to_string();
+            self.cursor = self.text.len();
+            self.anchor = None;
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn focused(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.set_text("");
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{wrap, Row, TextAreaComponent};
+    use crate::{
+        components::Component,
+        keys::KeyConfig,
+        queue::{InternalEvent, Queue},
+        settings::Settings,
+        ui::style::Theme,
+    };
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    fn type_text(area: &mut TextAreaComponent, text: &str) {
+        for c in text.chars() {
+            area.event(key(KeyCode::Char(c), KeyModifiers::empty()))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_wrap_at_spaces() {
+        assert_eq!(
+            wrap("one two three\n\nfour", 8),
+            vec![
+                Row { start: 0, end: 8 },
+                Row { start: 8, end: 13 },
+                Row { start: 14, end: 14 },
+                Row { start: 15, end: 19 },
+            ]
+        );
+        assert_eq!(wrap("abcdef", 4).len(), 2);
+    }
+
+    #[test]
+    fn test_select_undo_redo() {
+        let mut area = TextAreaComponent::new(
+            "Message",
+            "",
+            &Queue::new(),
+            Rc::new(Theme::default()),
+            Rc::new(RefCell::new(KeyConfig::default())),
+            Rc::new(Settings::default()),
+        );
+        area.show().unwrap();
+
+        type_text(&mut area, "hello world");
+        area.event(key(KeyCode::Enter, KeyModifiers::empty())).unwrap();
+        type_text(&mut area, "bye");
+        assert_eq!(area.text(), "hello world\nbye");
+
+        area.event(key(KeyCode::Char('z'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(area.text(), "hello world\n");
+
+        area.event(key(KeyCode::Up, KeyModifiers::empty())).unwrap();
+        area.event(key(KeyCode::End, KeyModifiers::empty())).unwrap();
+        for _ in 0..5 {
+            area.event(key(KeyCode::Left, KeyModifiers::SHIFT)).unwrap();
+        }
+        type_text(&mut area, "there");
+        assert_eq!(area.text(), "hello there\n");
+
+        area.event(key(KeyCode::Char('z'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(area.text(), "hello world\n");
+        area.event(key(KeyCode::Char('y'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(area.text(), "hello there\n");
+
+        area.event(key(KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(area.take_submitted().as_deref(), Some("hello there\n"));
+        assert!(!area.is_visible());
+    }
+
+    #[test]
+    fn test_editor_round_trip() {
+        let queue = Queue::new();
+        let mut area = TextAreaComponent::new(
+            "Message",
+            "",
+            &queue,
+            Rc::new(Theme::default()),
+            Rc::new(RefCell::new(KeyConfig::default())),
+            Rc::new(Settings::default()),
+        );
+        area.show().unwrap();
+        type_text(&mut area, "draft");
+        assert!(!area.edited(Some("stolen")).unwrap().is_consumed());
+
+        area.event(key(KeyCode::Char('e'), KeyModifiers::CONTROL))
+            .unwrap();
+        match queue.pop() {
+            Some(InternalEvent::OpenExternalEditor(text)) => {
+                assert!(text.starts_with("draft\n"));
+                assert!(text.contains("\n# "));
+            }
+            _ => panic!("no editor requested"),
+        }
+
+        assert!(area
+            .edited(Some("final\n# hint\nwords\n"))
+            .unwrap()
+            .is_consumed());
+        assert_eq!(area.text(), "final\nwords");
+        assert!(!area.edited(None).unwrap().is_consumed());
+    }
+}
@@ -0,0 +1,340 @@
+use std::{cell::Cell, collections::VecDeque, convert::TryFrom};
+
+use anyhow::Result;
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+use tui::{
+    backend::Backend,
+    Frame,
+    layout::Rect,
+    style::Modifier,
+    text::{Span, Spans},
+    widgets::{Block, Borders, BorderType, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    settings::SharedSettings,
+    strings, ui,
+    ui::style::SharedTheme,
+};
+
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, EventState, MsgKind,
+};
+
+/// how long toasts stay and how many show at once
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct ToastConfig {
+    /// ticks a toast stays on screen
+    pub ticks: u16,
+    /// toasts shown at once, older ones make room for new ones
+    pub limit: usize,
+}
+
+impl Default for ToastConfig {
+    fn default() -> Self {
+        Self {
+            ticks: 15,
+            limit: 3,
+        }
+    }
+}
+
+const HISTORY_LEN: usize = 100;
+const TOAST_WIDTH: u16 = 40;
+
+struct Toast {
+    kind: MsgKind,
+    text: String,
+    ticks_left: u16,
+}
+
+/// short notifications in the top right corner that go away
+/// on their own, the history popup lists all of them again
+pub struct ToastComponent {
+    toasts: VecDeque<Toast>,
+    history: VecDeque<(MsgKind, String)>,
+    visible: bool,
+    scroll: u16,
+    page_height: Cell<u16>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+    settings: SharedSettings,
+}
+
+impl DrawableComponent for ToastComponent {
+    /// draws the history popup, toasts are drawn by `draw_toasts`
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let size = f.size();
+            let area = ui::centered_rect_absolute(
+                size.width.saturating_sub(4).min(80),
+                size.height.saturating_sub(4).min(20),
+                size,
+            );
+            self.page_height.set(area.height.saturating_sub(2));
+
+            let lines: Vec<Spans> = if self.history.is_empty() {
+                vec![Spans::from(strings::notifications_empty(
+                    &self.key_config,
+                ))]
+            } else {
+                self.history
+                    .iter()
+                    .map(|(kind, text)| {
+                        Spans::from(vec![
+                            Span::styled(
+                                strings::notification_prefix(
+                                    &self.key_config,
+                                    *kind,
+                                ),
+                                self.theme.notification(*kind),
+                            ),
+                            Span::raw(text.as_str()),
+                        ])
+                    })
+                    .collect()
+            };
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(lines)
+                    .block(
+                        Block::default()
+                            .title(strings::notifications_title(
+                                &self.key_config,
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick),
+                    )
+                    .scroll((self.scroll, 0)),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ToastComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<EventState> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                let keys = self.key_config.borrow().popup.clone();
+                let page = self.page_height.get().max(1);
+                if e == keys.exit_popup {
+                    self.hide();
+                } else if e == keys.scroll_up {
+                    self.scroll = self.scroll.saturating_sub(1);
+                } else if e == keys.scroll_down {
+                    self.scroll_to(self.scroll.saturating_add(1));
+                } else if e == keys.page_up {
+                    self.scroll = self.scroll.saturating_sub(page);
+                } else if e == keys.page_down {
+                    self.scroll_to(self.scroll.saturating_add(page));
+                } else if e == keys.move_top {
+                    self.scroll = 0;
+                } else if e == keys.move_bottom {
+                    self.scroll_to(u16::MAX);
+                } else {
+                    return Ok(EventState::NotConsumed);
+                }
+                return Ok(EventState::Consumed);
+            }
+            return Ok(EventState::NotConsumed);
+        }
+
+        if let Event::Key(e) = ev {
+            if e == self.key_config.borrow().global.open_notifications {
+                self.show()?;
+                return Ok(EventState::Consumed);
+            }
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::Popup, "exit_popup") => self.hide(),
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    /// the history only reads, what it leaves goes on to the tab
+    fn is_modal(&self) -> bool {
+        false
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.scroll = 0;
+        self.visible = true;
+        Ok(())
+    }
+}
+
+impl ToastComponent {
+    pub fn new(
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+        settings: SharedSettings,
+    ) -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            history: VecDeque::new(),
+            visible: false,
+            scroll: 0,
+            page_height: Cell::new(0),
+            theme,
+            key_config,
+            settings,
+        }
+    }
+
+    /// shows `text` for the configured number of ticks
+    /// and records it in the history, newest first
+    pub fn push(&mut self, kind: MsgKind, text: String) {
+        let config = self.settings.toasts;
+
+        self.history.push_front((kind, text.clone()));
+        self.history.truncate(HISTORY_LEN);
+
+        self.toasts.push_back(Toast {
+            kind,
+            text,
+            ticks_left: config.ticks,
+        });
+        while self.toasts.len() > config.limit {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// counts down the toasts, returns `true` if one went away
+    pub fn on_tick(&mut self) -> bool {
+        let before = self.toasts.len();
+        for toast in &mut self.toasts {
+            toast.ticks_left = toast.ticks_left.saturating_sub(1);
+        }
+        self.toasts.retain(|t| t.ticks_left > 0);
+        before != self.toasts.len()
+    }
+
+    /// stacks the toasts downwards from the top right of `rect`,
+    /// toasts dim in the last third of their time
+    pub fn draw_toasts<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let fade_at = self.settings.toasts.ticks / 3;
+        let mut y = rect.y;
+
+        for toast in &self.toasts {
+            let width = u16::try_from(toast.text.width())
+                .unwrap_or(u16::MAX)
+                .saturating_add(2)
+                .min(TOAST_WIDTH)
+                .min(rect.width);
+            if y.saturating_add(3) > rect.bottom() {
+                break;
+            }
+            let area = Rect::new(rect.right() - width, y, width, 3);
+
+            let mut style = self.theme.notification(toast.kind);
+            if toast.ticks_left <= fade_at {
+                style = style.add_modifier(Modifier::DIM);
+            }
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(Span::styled(toast.text.as_str(), style))
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(style),
+                    ),
+                area,
+            );
+            y += 3;
+        }
+    }
+
+    fn scroll_to(&mut self, scroll: u16) {
+        let max = u16::try_from(self.history.len())
+            .unwrap_or(u16::MAX)
+            .saturating_sub(self.page_height.get());
+        self.scroll = scroll.min(max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{ToastComponent, ToastConfig};
+    use crate::{
+        components::MsgKind, keys::KeyConfig, settings::Settings,
+        ui::style::Theme,
+    };
+
+    #[test]
+    fn test_toasts_expire_and_stay_in_history() {
+        let settings = Settings {
+            toasts: ToastConfig { ticks: 2, limit: 2 },
+            ..Settings::default()
+        };
+        let mut toasts = ToastComponent::new(
+            Rc::new(Theme::default()),
+            Rc::new(RefCell::new(KeyConfig::default())),
+            Rc::new(settings),
+        );
+
+        toasts.push(MsgKind::Info, "a".to_string());
+        toasts.push(MsgKind::Success, "b".to_string());
+        toasts.push(MsgKind::Error, "c".to_string());
+        assert_eq!(toasts.toasts.len(), 2);
+
+        assert!(!toasts.on_tick());
+        assert!(toasts.on_tick());
+        assert!(toasts.toasts.is_empty());
+        assert_eq!(toasts.history.len(), 3);
+        assert_eq!(toasts.history[0].1, "c");
+    }
+}
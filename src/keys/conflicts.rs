@@ -0,0 +1,587 @@
+use itertools::Itertools;
+
+use super::{KeyBinding, KeyConfig};
+use crate::components::input_char;
+
+/// places in the ui that resolve key events on their own
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyScope {
+    Global,
+    Help,
+    TabT01,
+    Login,
+    TabT02,
+    Search,
+    TabT03,
+    PopInput,
+    TextArea,
+    Form,
+    KeysEditor,
+    Palette,
+    Confirm,
+    Msg,
+    Notifications,
+}
+
+impl KeyScope {
+    pub const ALL: [Self; 15] = [
+        Self::Global,
+        Self::Help,
+        Self::TabT01,
+        Self::Login,
+        Self::TabT02,
+        Self::Search,
+        Self::TabT03,
+        Self::PopInput,
+        Self::TextArea,
+        Self::Form,
+        Self::KeysEditor,
+        Self::Palette,
+        Self::Confirm,
+        Self::Msg,
+        Self::Notifications,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Help => "help popup",
+            Self::TabT01 => "tab T01",
+            Self::Login => "login popup",
+            Self::TabT02 => "tab T02",
+            Self::Search => "search input",
+            Self::TabT03 => "tab T03",
+            Self::PopInput => "input popup",
+            Self::TextArea => "message editor",
+            Self::Form => "entry form",
+            Self::KeysEditor => "key binding editor",
+            Self::Palette => "command palette",
+            Self::Confirm => "confirm dialog",
+            Self::Msg => "message popup",
+            Self::Notifications => "notification history",
+        }
+    }
+}
+
+/// a single problem found by `KeyConfig::check_conflicts`
+#[derive(Debug, PartialEq)]
+pub enum KeyIssue {
+    /// several actions that are resolved together share a key
+    Conflict {
+        scope: KeyScope,
+        bindings: Vec<KeyBinding>,
+    },
+    /// an action can never fire in `scope` because
+    /// an action consulted earlier consumes its key
+    Shadowed {
+        scope: KeyScope,
+        binding: KeyBinding,
+        by: KeyBinding,
+    },
+}
+
+/// result of `KeyConfig::check_conflicts`
+#[derive(Debug, Default)]
+pub struct ConflictReport {
+    pub issues: Vec<KeyIssue>,
+}
+
+impl ConflictReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|i| matches!(i, KeyIssue::Conflict { .. }))
+    }
+
+    /// one human readable line per issue
+    pub fn lines(&self, key_config: &KeyConfig) -> Vec<String> {
+        self.issues
+            .iter()
+            .map(|issue| match issue {
+                KeyIssue::Conflict { scope, bindings } => format!(
+                    "conflict in {}: [{}] is bound to {}",
+                    scope.name(),
+                    key_config.get_hint(bindings[0].key),
+                    bindings
+                        .iter()
+                        .map(KeyBinding::path)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                KeyIssue::Shadowed { scope, binding, by } => format!(
+                    "shadowed in {}: '{}' [{}] is hidden by '{}'",
+                    scope.name(),
+                    binding.path(),
+                    key_config.get_hint(binding.key),
+                    by.path()
+                ),
+            })
+            .collect()
+    }
+}
+
+fn pick(bindings: Vec<KeyBinding>, actions: &[&str]) -> Vec<KeyBinding> {
+    bindings
+        .into_iter()
+        .filter(|b| actions.contains(&b.action))
+        .collect()
+}
+
+/// leaves out what a text input takes as typing
+fn untyped(bindings: Vec<KeyBinding>) -> Vec<KeyBinding> {
+    bindings
+        .into_iter()
+        .filter(|b| input_char(b.key).is_none())
+        .collect()
+}
+
+impl KeyConfig {
+    /// bindings that are live in `scope`, grouped in the order
+    /// they get to see a key (mirrors the `event` implementations)
+    pub fn scope_layers(&self, scope: KeyScope) -> Vec<Vec<KeyBinding>> {
+        let global = self.global.bindings();
+        let editing = pick(
+            self.text_input.bindings(),
+            &[
+                "confirm",
+                "cancel",
+                "delete_back",
+                "delete_forward",
+                "cursor_left",
+                "cursor_right",
+            ],
+        );
+
+        match scope {
+            KeyScope::Global => vec![global],
+            KeyScope::Help => {
+                let mut layer = pick(
+                    self.popup.bindings(),
+                    &[
+                        "exit_popup",
+                        "scroll_up",
+                        "scroll_down",
+                        "page_up",
+                        "page_down",
+                        "move_top",
+                        "move_bottom",
+                    ],
+                );
+                layer.extend(pick(
+                    self.text_input.bindings(),
+                    &["delete_back"],
+                ));
+                vec![layer]
+            }
+            KeyScope::KeysEditor => vec![self.popup.bindings()],
+            KeyScope::Palette => {
+                let mut layer = editing.clone();
+                layer.extend(pick(
+                    self.popup.bindings(),
+                    &["scroll_up", "scroll_down"],
+                ));
+                vec![layer]
+            }
+            KeyScope::Confirm => {
+                let mut layer = pick(
+                    self.popup.bindings(),
+                    &["exit_popup", "select"],
+                );
+                layer.extend(pick(
+                    self.text_input.bindings(),
+                    &["cursor_left", "cursor_right", "focus_next", "focus_prev"],
+                ));
+                vec![layer, pick(global.clone(), &["exit"])]
+            }
+            KeyScope::Msg => vec![
+                pick(self.popup.bindings(), &["exit_popup", "select"]),
+                pick(global.clone(), &["exit"]),
+            ],
+            // not modal, the current tab gets the rest before the globals
+            KeyScope::Notifications => vec![
+                pick(
+                    self.popup.bindings(),
+                    &[
+                        "exit_popup",
+                        "scroll_up",
+                        "scroll_down",
+                        "page_up",
+                        "page_down",
+                        "move_top",
+                        "move_bottom",
+                    ],
+                ),
+                global.clone(),
+            ],
+            KeyScope::TabT01 => {
+                vec![pick(self.tab.bindings(), &["login"]), global]
+            }
+            // the dialog takes every key but exit while it is open
+            KeyScope::Login | KeyScope::Form => {
+                vec![self.text_input.bindings(), pick(global, &["exit"])]
+            }
+            KeyScope::TabT02 => vec![
+                pick(
+                    self.tab.bindings(),
+                    &[
+                        "focus_left",
+                        "focus_right",
+                        "focus_above",
+                        "focus_below",
+                        "focus_next",
+                        "focus_prev",
+                        "grow_pane",
+                        "shrink_pane",
+                        "toggle_workarea",
+                    ],
+                ),
+                self.list.bindings(),
+                pick(self.tab.bindings(), &["search"]),
+                global,
+            ],
+            // printable characters get typed into the input
+            KeyScope::Search | KeyScope::PopInput => {
+                let mut layer = editing;
+                layer.extend(pick(self.text_input.bindings(), &["open_editor"]));
+                vec![layer, untyped(global)]
+            }
+            KeyScope::TabT03 => vec![
+                pick(
+                    self.tab.bindings(),
+                    &["open_input", "open_message", "open_form"],
+                ),
+                global,
+            ],
+            // the editor takes every key but exit while it is open
+            KeyScope::TextArea => {
+                let mut layer = self.text_area.bindings();
+                layer.extend(pick(
+                    self.text_input.bindings(),
+                    &[
+                        "cancel",
+                        "delete_back",
+                        "delete_forward",
+                        "cursor_left",
+                        "cursor_right",
+                        "open_editor",
+                    ],
+                ));
+                vec![layer, pick(global, &["exit"])]
+            }
+        }
+    }
+
+    /// scopes in which `binding` is consulted at all
+    pub fn binding_scopes(&self, binding: &KeyBinding) -> Vec<KeyScope> {
+        KeyScope::ALL
+            .iter()
+            .copied()
+            .filter(|scope| {
+                self.scope_layers(*scope)
+                    .iter()
+                    .flatten()
+                    .any(|b| b.id() == binding.id())
+            })
+            .collect()
+    }
+
+    /// every binding with the scopes it is consulted in,
+    /// the list the help popup and `--print-keys` show
+    pub fn help_entries(&self) -> Vec<(KeyBinding, String)> {
+        self.bindings()
+            .into_iter()
+            .map(|b| {
+                let scopes = self
+                    .binding_scopes(&b)
+                    .into_iter()
+                    .map(KeyScope::name)
+                    .join(", ");
+                (b, scopes)
+            })
+            .collect()
+    }
+
+    /// reports keys bound twice within one layer and
+    /// actions hidden by a layer that sees the key first
+    pub fn check_conflicts(&self) -> ConflictReport {
+        let mut report = ConflictReport::default();
+
+        for scope in KeyScope::ALL.iter().copied() {
+            let layers = self.scope_layers(scope);
+
+            for (n, layer) in layers.iter().enumerate() {
+                for (i, b) in layer.iter().enumerate() {
+                    if layer[..i].iter().any(|o| o.key == b.key) {
+                        continue;
+                    }
+                    let bindings: Vec<KeyBinding> = layer
+                        .iter()
+                        .filter(|o| o.key == b.key)
+                        .copied()
+                        .collect();
+                    if bindings.len() > 1 {
+                        report
+                            .issues
+                            .push(KeyIssue::Conflict { scope, bindings });
+                    }
+
+                    if let Some(by) = layers[..n]
+                        .iter()
+                        .flatten()
+                        .find(|o| o.key == b.key)
+                    {
+                        report.issues.push(KeyIssue::Shadowed {
+                            scope,
+                            binding: *b,
+                            by: *by,
+                        });
+                    }
+                }
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{KeyConfig, KeyIssue, KeyScope};
+    use crate::keys::KeyContext;
+    use crate::{
+        components::{
+            CommandPaletteComponent, CommandText, Component,
+            ConfirmComponent, HelpComponent, KeysEditorComponent,
+            MsgComponent, MsgKind, ToastComponent,
+        },
+        keys::SharedKeyConfig,
+        queue::{Action, Queue},
+        settings::Settings,
+        tabs,
+        ui::style::Theme,
+    };
+
+    /// what handles the keys of `scope`, in the state the scope stands for
+    fn handler(scope: KeyScope, key_config: &SharedKeyConfig) -> Box<dyn Component> {
+        let theme = Rc::new(Theme::default());
+        let queue = Queue::new();
+        let settings = Rc::new(Settings::default());
+        let mut tabs = tabs::default_tabs(&queue, &theme, key_config, &settings);
+        let mut tab = |index: usize, open: Option<KeyEvent>| {
+            let mut tab: Box<dyn Component> = tabs.remove(index);
+            tab.show().unwrap();
+            if let Some(key) = open {
+                assert!(tab.event(Event::Key(key)).unwrap().is_consumed());
+            }
+            tab
+        };
+
+        match scope {
+            KeyScope::Global => unreachable!(),
+            KeyScope::Help => {
+                let mut help = HelpComponent::new(theme, key_config.clone());
+                help.show().unwrap();
+                Box::new(help)
+            }
+            KeyScope::TabT01 => tab(0, None),
+            KeyScope::Login => tab(0, Some(key_config.borrow().tab.login)),
+            KeyScope::TabT02 => tab(1, None),
+            KeyScope::Search => tab(1, Some(key_config.borrow().tab.search)),
+            KeyScope::TabT03 => tab(2, None),
+            KeyScope::PopInput => tab(2, Some(key_config.borrow().tab.open_input)),
+            KeyScope::TextArea => tab(2, Some(key_config.borrow().tab.open_message)),
+            KeyScope::Form => tab(2, Some(key_config.borrow().tab.open_form)),
+            KeyScope::KeysEditor => {
+                // no save file, nothing touches the user's config
+                let mut editor = KeysEditorComponent::new(
+                    None,
+                    &queue,
+                    theme,
+                    key_config.clone(),
+                );
+                editor.show().unwrap();
+                Box::new(editor)
+            }
+            KeyScope::Palette => {
+                let mut palette =
+                    CommandPaletteComponent::new(theme, key_config.clone());
+                palette
+                    .open(vec![
+                        CommandText::new("a".to_string(), "first", "test"),
+                        CommandText::new("b".to_string(), "second", "test"),
+                    ])
+                    .unwrap();
+                Box::new(palette)
+            }
+            KeyScope::Confirm => {
+                let mut confirm =
+                    ConfirmComponent::new(queue, theme, key_config.clone());
+                confirm
+                    .open(Action::RemoveAllLines {
+                        list: "list".to_string(),
+                    })
+                    .unwrap();
+                Box::new(confirm)
+            }
+            KeyScope::Msg => {
+                let mut msg = MsgComponent::new(theme, key_config.clone());
+                msg.show(MsgKind::Info, "msg".to_string());
+                Box::new(msg)
+            }
+            KeyScope::Notifications => {
+                let mut toasts = ToastComponent::new(
+                    theme,
+                    key_config.clone(),
+                    Rc::new(Settings::default()),
+                );
+                toasts.show().unwrap();
+                Box::new(toasts)
+            }
+        }
+    }
+
+    /// whatever a scope lists with an action can be run from the palette
+    #[test]
+    fn test_command_actions_run() {
+        let key_config: SharedKeyConfig =
+            Rc::new(RefCell::new(KeyConfig::default()));
+
+        for scope in KeyScope::ALL.iter().copied() {
+            if scope == KeyScope::Global {
+                continue;
+            }
+            let mut commands = Vec::new();
+            handler(scope, &key_config).commands(&mut commands, false);
+            let actions = commands
+                .iter()
+                .filter(|c| c.enabled && c.available)
+                .filter_map(|c| c.text.action)
+                // global ones are the app's
+                .filter(|a| a.context != KeyContext::Global);
+            for action in actions {
+                assert!(
+                    handler(scope, &key_config)
+                        .run_action(action)
+                        .unwrap()
+                        .is_consumed(),
+                    "{} ignores {:?}",
+                    scope.name(),
+                    action
+                );
+            }
+        }
+    }
+
+    /// the tables of `scope_layers` are written by hand, this checks
+    /// them against the handlers: every binding of a scope's own layers
+    /// gets consumed, keys the table passes on to the global keys do not
+    #[test]
+    fn test_layers_match_handlers() {
+        let key_config: SharedKeyConfig =
+            Rc::new(RefCell::new(KeyConfig::default()));
+        let keys = key_config.borrow().clone();
+        let consumes = |scope: KeyScope, key: KeyEvent| {
+            handler(scope, &key_config)
+                .event(Event::Key(key))
+                .unwrap()
+                .is_consumed()
+        };
+
+        for scope in KeyScope::ALL.iter().copied() {
+            if scope == KeyScope::Global {
+                continue;
+            }
+            let layers = keys.scope_layers(scope);
+            let (own, passed): (Vec<_>, Vec<_>) = layers
+                .into_iter()
+                .partition(|l| l.iter().any(|b| b.context != KeyContext::Global));
+            let own: Vec<_> = own.into_iter().flatten().collect();
+            let passed: Vec<_> = passed.into_iter().flatten().collect();
+
+            for b in &own {
+                assert!(
+                    consumes(scope, b.key),
+                    "{} ignores '{}'",
+                    scope.name(),
+                    b.path()
+                );
+            }
+
+            // a modal popup or an input passes on only what `passed`
+            // lists, everything else falls through to the global keys
+            let others = if passed.len() < keys.global.bindings().len() {
+                passed
+            } else {
+                keys.bindings()
+            };
+            for b in others {
+                if own.iter().any(|o| o.key == b.key) {
+                    continue;
+                }
+                assert!(
+                    !consumes(scope, b.key),
+                    "{} takes '{}' but its table passes it on",
+                    scope.name(),
+                    b.path()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        let report = KeyConfig::default().check_conflicts();
+        assert!(report.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_conflict_in_same_layer() {
+        let mut config = KeyConfig::default();
+        config.list.remove_line = config.list.move_up;
+
+        let report = config.check_conflicts();
+
+        assert!(report.has_conflicts());
+        assert_eq!(report.issues.len(), 1);
+        match &report.issues[0] {
+            KeyIssue::Conflict { scope, bindings } => {
+                assert_eq!(*scope, KeyScope::TabT02);
+                assert_eq!(
+                    bindings.iter().map(|b| b.action).collect::<Vec<_>>(),
+                    vec!["move_up", "remove_line"]
+                );
+            }
+            issue => panic!("unexpected issue {:?}", issue),
+        }
+    }
+
+    #[test]
+    fn test_tab_shadows_global() {
+        let mut config = KeyConfig::default();
+        config.tab.login = KeyEvent {
+            code: KeyCode::Char('q'),
+            modifiers: KeyModifiers::empty(),
+        };
+
+        let report = config.check_conflicts();
+
+        assert!(!report.has_conflicts());
+        assert_eq!(report.issues.len(), 1);
+        match &report.issues[0] {
+            KeyIssue::Shadowed { scope, binding, by } => {
+                assert_eq!(*scope, KeyScope::TabT01);
+                assert_eq!(binding.path(), "global.quit");
+                assert_eq!(by.path(), "tab.login");
+            }
+            issue => panic!("unexpected issue {:?}", issue),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fmt::Write, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use ron::ser::{PrettyConfig, to_string_pretty};
+
+use super::KeyConfig;
+
+/// output formats of `--print-keys`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KeyExportFormat {
+    Markdown,
+    Text,
+    Ron,
+}
+
+impl KeyExportFormat {
+    pub const NAMES: [&'static str; 3] = ["markdown", "text", "ron"];
+}
+
+impl FromStr for KeyExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "markdown" => Ok(Self::Markdown),
+            "text" => Ok(Self::Text),
+            "ron" => Ok(Self::Ron),
+            _ => Err(anyhow!("unknown key export format: {}", s)),
+        }
+    }
+}
+
+impl KeyConfig {
+    /// every binding grouped by context, the same list the help popup
+    /// shows, ron gives a key config file with just the bindings
+    pub fn export(&self, format: KeyExportFormat) -> Result<String> {
+        if format == KeyExportFormat::Ron {
+            return Ok(to_string_pretty(self, PrettyConfig::default())?);
+        }
+
+        let mut out = String::new();
+        for (context, group) in
+            &self.help_entries().into_iter().group_by(|(b, _)| b.context)
+        {
+            match format {
+                KeyExportFormat::Markdown => {
+                    writeln!(out, "## {}\n", context.name())?;
+                    writeln!(out, "| Action | Key | Description | Used in |")?;
+                    writeln!(out, "|---|---|---|---|")?;
+                }
+                _ => writeln!(out, "{}", context.name().to_uppercase())?,
+            }
+
+            for (b, scopes) in group {
+                let key = self.get_hint(b.key);
+                match format {
+                    KeyExportFormat::Markdown => writeln!(
+                        out,
+                        "| {} | `{}` | {} | {} |",
+                        b.title,
+                        key.replace('|', "\\|"),
+                        b.desc,
+                        scopes
+                    )?,
+                    _ => writeln!(
+                        out,
+                        "    {:<12} {}\n        {} ({})",
+                        key, b.title, b.desc, scopes
+                    )?,
+                }
+            }
+            writeln!(out)?;
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyConfig, KeyExportFormat};
+
+    #[test]
+    fn test_export_markdown() {
+        let out =
+            KeyConfig::default().export(KeyExportFormat::Markdown).unwrap();
+
+        assert!(out.starts_with("## Global\n"));
+        assert!(out.contains("| Quit | `q` | quit application |"));
+        assert!(out.contains("## Popup\n"));
+    }
+
+    #[test]
+    fn test_export_ron_is_a_key_config() {
+        let config = KeyConfig::default();
+        let out = config.export(KeyExportFormat::Ron).unwrap();
+
+        assert_eq!(ron::de::from_str::<KeyConfig>(&out).unwrap(), config);
+    }
+}
@@ -0,0 +1,160 @@
+//TODO: remove once fixed https://github.com/rust-lang/rust-clippy/issues/6818
+#![allow(clippy::use_self)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use super::KeyConfig;
+
+/// how keys are printed in tabs, command bar and help
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum KeyHintStyle {
+    /// `^⇧x`
+    #[default]
+    Unicode,
+    /// `C-S-x`
+    Ascii,
+    /// `Ctrl+Shift+X`
+    Verbose,
+}
+
+impl KeyHintStyle {
+    fn modifier(self, modifier: KeyModifiers) -> &'static str {
+        match (self, modifier) {
+            (Self::Unicode, KeyModifiers::CONTROL) => "^",
+            (Self::Unicode, KeyModifiers::ALT) => "\u{2325}", //⌥
+            (Self::Unicode, _) => "\u{21e7}",                 //⇧
+            (Self::Ascii, KeyModifiers::CONTROL) => "C-",
+            (Self::Ascii, KeyModifiers::ALT) => "M-",
+            (Self::Ascii, _) => "S-",
+            (Self::Verbose, KeyModifiers::CONTROL) => "Ctrl+",
+            (Self::Verbose, KeyModifiers::ALT) => "Alt+",
+            (Self::Verbose, _) => "Shift+",
+        }
+    }
+
+    fn key(self, code: KeyCode) -> String {
+        let name = match (self, code) {
+            (_, KeyCode::Char(' ')) if self != Self::Unicode => "Space",
+            (_, KeyCode::Char(c)) => return c.to_string(),
+            (_, KeyCode::F(n)) => return format!("F{}", n),
+            (_, KeyCode::Null) => "",
+            (Self::Unicode, KeyCode::Enter) => "\u{23ce}", //⏎
+            (Self::Unicode, KeyCode::Left) => "\u{2190}",  //←
+            (Self::Unicode, KeyCode::Right) => "\u{2192}", //→
+            (Self::Unicode, KeyCode::Up) => "\u{2191}",    //↑
+            (Self::Unicode, KeyCode::Down) => "\u{2193}",  //↓
+            (Self::Unicode, KeyCode::Backspace) => "\u{232b}", //⌫
+            (Self::Unicode, KeyCode::Home) => "\u{2912}",  //⤒
+            (Self::Unicode, KeyCode::End) => "\u{2913}",   //⤓
+            (Self::Unicode, KeyCode::PageUp) => "\u{21de}", //⇞
+            (Self::Unicode, KeyCode::PageDown) => "\u{21df}", //⇟
+            (Self::Unicode, KeyCode::Tab) => "\u{21e5}",   //⇥
+            (Self::Unicode, KeyCode::BackTab) => "\u{21e4}", //⇤
+            (Self::Unicode, KeyCode::Delete) => "\u{2326}", //⌦
+            (Self::Unicode, KeyCode::Insert) => "\u{2380}", //⎀
+            (Self::Unicode, KeyCode::Esc) => "\u{238b}",   //⎋
+            (_, KeyCode::Enter) => "Enter",
+            (_, KeyCode::Left) => "Left",
+            (_, KeyCode::Right) => "Right",
+            (_, KeyCode::Up) => "Up",
+            (_, KeyCode::Down) => "Down",
+            (_, KeyCode::Backspace) => "Backspace",
+            (_, KeyCode::Home) => "Home",
+            (_, KeyCode::End) => "End",
+            (Self::Ascii, KeyCode::PageUp) => "PgUp",
+            (Self::Ascii, KeyCode::PageDown) => "PgDn",
+            (_, KeyCode::PageUp) => "PageUp",
+            (_, KeyCode::PageDown) => "PageDown",
+            (_, KeyCode::Tab) => "Tab",
+            (_, KeyCode::BackTab) => "BackTab",
+            (Self::Ascii, KeyCode::Delete) => "Del",
+            (Self::Ascii, KeyCode::Insert) => "Ins",
+            (_, KeyCode::Delete) => "Delete",
+            (_, KeyCode::Insert) => "Insert",
+            (_, KeyCode::Esc) => "Esc",
+        };
+        name.to_string()
+    }
+
+    /// every set modifier in a fixed order: ctrl, alt, shift
+    fn modifiers(self, modifiers: KeyModifiers) -> String {
+        [
+            KeyModifiers::CONTROL,
+            KeyModifiers::ALT,
+            KeyModifiers::SHIFT,
+        ]
+        .iter()
+        .filter(|m| modifiers.contains(**m))
+        .map(|m| self.modifier(*m))
+        .collect()
+    }
+
+    pub fn hint(self, ev: KeyEvent) -> String {
+        let key = match (self, ev.code) {
+            (Self::Verbose, KeyCode::Char(c)) => {
+                c.to_uppercase().to_string()
+            }
+            (_, code) => self.key(code),
+        };
+        let modifiers = self.modifiers(ev.modifiers);
+
+        if key.is_empty() {
+            modifiers.trim_end_matches(&['-', '+'][..]).to_string()
+        } else {
+            format!("{}{}", modifiers, key)
+        }
+    }
+}
+
+impl KeyConfig {
+    pub fn get_hint(&self, ev: KeyEvent) -> String {
+        self.hint_style.hint(ev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::KeyHintStyle;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent { code, modifiers }
+    }
+
+    #[test]
+    fn test_combined_modifiers() {
+        let ev = key(
+            KeyCode::Char('X'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        );
+
+        assert_eq!(KeyHintStyle::Unicode.hint(ev), "^\u{21e7}X");
+        assert_eq!(KeyHintStyle::Ascii.hint(ev), "C-S-X");
+        assert_eq!(KeyHintStyle::Verbose.hint(ev), "Ctrl+Shift+X");
+    }
+
+    #[test]
+    fn test_function_and_named_keys() {
+        let f5 = key(KeyCode::F(5), KeyModifiers::ALT);
+        let pgdn = key(KeyCode::PageDown, KeyModifiers::empty());
+
+        assert_eq!(KeyHintStyle::Unicode.hint(f5), "\u{2325}F5");
+        assert_eq!(KeyHintStyle::Ascii.hint(f5), "M-F5");
+        assert_eq!(KeyHintStyle::Verbose.hint(f5), "Alt+F5");
+        assert_eq!(KeyHintStyle::Ascii.hint(pgdn), "PgDn");
+        assert_eq!(KeyHintStyle::Verbose.hint(pgdn), "PageDown");
+    }
+
+    #[test]
+    fn test_modifiers_only() {
+        let ev = key(
+            KeyCode::Null,
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+
+        assert_eq!(KeyHintStyle::Ascii.hint(ev), "C-M");
+        assert_eq!(KeyHintStyle::Verbose.hint(ev), "Ctrl+Alt");
+    }
+}
@@ -0,0 +1,246 @@
+//TODO: remove once fixed https://github.com/rust-lang/rust-clippy/issues/6818
+#![allow(clippy::use_self)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+/// groups of actions that are resolved together
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeyContext {
+    Global,
+    Tab,
+    List,
+    TextInput,
+    TextArea,
+    Popup,
+}
+
+impl KeyContext {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Global => "Global",
+            Self::Tab => "Tab",
+            Self::List => "List",
+            Self::TextInput => "Text Input",
+            Self::TextArea => "Text Area",
+            Self::Popup => "Popup",
+        }
+    }
+
+    /// section name in the config file
+    pub const fn config_name(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Tab => "tab",
+            Self::List => "list",
+            Self::TextInput => "text_input",
+            Self::TextArea => "text_area",
+            Self::Popup => "popup",
+        }
+    }
+}
+
+/// identifies an action independent of its current key
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActionId {
+    pub context: KeyContext,
+    pub action: &'static str,
+}
+
+impl ActionId {
+    pub const fn new(context: KeyContext, action: &'static str) -> Self {
+        Self { context, action }
+    }
+}
+
+/// one row of a keymap table
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    /// field name in the config file
+    pub action: &'static str,
+    pub key: KeyEvent,
+    pub title: &'static str,
+    pub desc: &'static str,
+}
+
+impl KeyBinding {
+    pub const fn id(&self) -> ActionId {
+        ActionId::new(self.context, self.action)
+    }
+
+    /// `context.action` as written in the config file
+    pub fn path(&self) -> String {
+        format!("{}.{}", self.context.config_name(), self.action)
+    }
+}
+
+/// declares a keymap struct together with its defaults
+/// and its table of `KeyBinding`s so they can never drift apart
+macro_rules! keymap {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $context:expr,
+        [$($action:ident: ($code:expr, $mods:expr), $title:expr, $desc:expr;)+]
+    ) => {
+        $(#[$meta])*
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+        #[serde(default)]
+        pub struct $name {
+            $(pub $action: KeyEvent,)+
+        }
+
+        #[rustfmt::skip]
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($action: KeyEvent { code: $code, modifiers: $mods },)+
+                }
+            }
+        }
+
+        impl $name {
+            /// mutable access to a binding by its config file name
+            pub fn get_mut(&mut self, action: &str) -> Option<&mut KeyEvent> {
+                match action {
+                    $(stringify!($action) => Some(&mut self.$action),)+
+                    _ => None,
+                }
+            }
+
+            pub fn bindings(&self) -> Vec<KeyBinding> {
+                vec![
+                    $(KeyBinding {
+                        context: $context,
+                        action: stringify!($action),
+                        key: self.$action,
+                        title: $title,
+                        desc: $desc,
+                    },)+
+                ]
+            }
+        }
+    };
+}
+
+const NONE: KeyModifiers = KeyModifiers::empty();
+
+keymap!(
+    /// always available unless a popup consumes the key
+    GlobalKeys, KeyContext::Global, [
+    quit: (KeyCode::Char('q'), NONE), "Quit", "quit application";
+    exit: (KeyCode::Char('c'), KeyModifiers::CONTROL), "Exit", "exit application from anywhere";
+    open_help: (KeyCode::Char('h'), NONE), "Help", "open this help screen";
+    tab_toggle: (KeyCode::Char(']'), NONE), "Next", "switch to next tab";
+    tab_toggle_reverse: (KeyCode::Char('['), NONE), "Previous", "switch to previous tab";
+    tab_move_left: (KeyCode::Left, KeyModifiers::SHIFT), "Move Tab Left", "move the current tab one place to the left";
+    tab_move_right: (KeyCode::Right, KeyModifiers::SHIFT), "Move Tab Right", "move the current tab one place to the right";
+    tab_hide: (KeyCode::Char('w'), KeyModifiers::CONTROL), "Hide Tab", "hide the current tab";
+    tab_show_all: (KeyCode::Char('T'), KeyModifiers::SHIFT), "Show Tabs", "show all hidden tabs again";
+    tab_1: (KeyCode::Char('1'), NONE), "Tab 1", "switch to tab 1";
+    tab_2: (KeyCode::Char('2'), NONE), "Tab 2", "switch to tab 2";
+    tab_3: (KeyCode::Char('3'), NONE), "Tab 3", "switch to tab 3";
+    tab_4: (KeyCode::Char('4'), NONE), "Tab 4", "switch to tab 4";
+    tab_5: (KeyCode::Char('5'), NONE), "Tab 5", "switch to tab 5";
+    tab_6: (KeyCode::Char('6'), NONE), "Tab 6", "switch to tab 6";
+    tab_7: (KeyCode::Char('7'), NONE), "Tab 7", "switch to tab 7";
+    tab_8: (KeyCode::Char('8'), NONE), "Tab 8", "switch to tab 8";
+    tab_9: (KeyCode::Char('9'), NONE), "Tab 9", "switch to tab 9";
+    cmd_bar_toggle: (KeyCode::Char('.'), NONE), "More", "expand or collapse the command bar";
+    cmd_bar_next: (KeyCode::Char('>'), KeyModifiers::SHIFT), "Next Command", "select the next entry of the command bar";
+    cmd_bar_prev: (KeyCode::Char('<'), KeyModifiers::SHIFT), "Previous Command", "select the previous entry of the command bar";
+    open_key_editor: (KeyCode::Char('K'), KeyModifiers::SHIFT), "Keys", "edit key bindings";
+    open_palette: (KeyCode::Char('p'), KeyModifiers::CONTROL), "Commands", "search and run a command";
+    open_notifications: (KeyCode::Char('N'), KeyModifiers::SHIFT), "Notifications", "list past notifications";
+    logout: (KeyCode::Char('O'), KeyModifiers::SHIFT), "Logout", "end the session of the logged in user";
+]);
+
+impl GlobalKeys {
+    /// number key of the tab at `index`, tabs past the ninth get none
+    pub fn tab_key(&self, index: usize) -> Option<KeyEvent> {
+        [
+            self.tab_1, self.tab_2, self.tab_3, self.tab_4, self.tab_5,
+            self.tab_6, self.tab_7, self.tab_8, self.tab_9,
+        ]
+        .get(index)
+        .copied()
+    }
+}
+
+keymap!(
+    /// actions of the currently selected tab
+    TabKeys, KeyContext::Tab, [
+    login: (KeyCode::Char('l'), NONE), "Login", "open the login dialog";
+    search: (KeyCode::Char('s'), NONE), "Search", "enable search input";
+    focus_left: (KeyCode::Left, NONE), "Focus Left", "focus the pane on the left";
+    focus_right: (KeyCode::Right, NONE), "Focus Right", "focus the pane on the right";
+    focus_above: (KeyCode::Up, KeyModifiers::CONTROL), "Focus Above", "focus the pane above";
+    focus_below: (KeyCode::Down, KeyModifiers::CONTROL), "Focus Below", "focus the pane below";
+    focus_next: (KeyCode::Tab, NONE), "Focus Next", "cycle focus forward through the panes";
+    focus_prev: (KeyCode::BackTab, KeyModifiers::SHIFT), "Focus Previous", "cycle focus backward through the panes";
+    grow_pane: (KeyCode::Char('+'), NONE), "Grow", "grow the focused pane";
+    shrink_pane: (KeyCode::Char('-'), NONE), "Shrink", "shrink the focused pane";
+    toggle_workarea: (KeyCode::Char('w'), NONE), "Maximize", "maximize the focused pane or restore the layout";
+    open_input: (KeyCode::Char('i'), NONE), "Input", "open the input popup";
+    open_message: (KeyCode::Char('m'), NONE), "Message", "open the message editor";
+    open_form: (KeyCode::Char('f'), NONE), "Entry", "open the entry form";
+]);
+
+keymap!(
+    /// navigation inside the focused list
+    ListKeys, KeyContext::List, [
+    move_up: (KeyCode::Up, NONE), "Up", "select previous line";
+    move_down: (KeyCode::Down, NONE), "Down", "select next line";
+    remove_line: (KeyCode::Char('D'), KeyModifiers::SHIFT), "Remove", "remove selected line";
+    remove_all: (KeyCode::Char('d'), KeyModifiers::CONTROL), "Remove All", "remove every line";
+]);
+
+keymap!(
+    /// editing of single line inputs
+    TextInputKeys, KeyContext::TextInput, [
+    confirm: (KeyCode::Enter, NONE), "Confirm", "submit the input";
+    cancel: (KeyCode::Esc, NONE), "Cancel", "discard the input";
+    delete_back: (KeyCode::Backspace, NONE), "Backspace", "delete character before cursor";
+    delete_forward: (KeyCode::Delete, NONE), "Delete", "delete character under cursor";
+    cursor_left: (KeyCode::Left, NONE), "Left", "move cursor left";
+    cursor_right: (KeyCode::Right, NONE), "Right", "move cursor right";
+    next_field: (KeyCode::Down, NONE), "Next Field", "focus next input field";
+    prev_field: (KeyCode::Up, NONE), "Previous Field", "focus previous input field";
+    focus_next: (KeyCode::Tab, NONE), "Focus Next", "cycle focus forward through the dialog";
+    focus_prev: (KeyCode::BackTab, KeyModifiers::SHIFT), "Focus Previous", "cycle focus backward through the dialog";
+    reveal_password: (KeyCode::Char('r'), KeyModifiers::CONTROL), "Reveal", "show the password for a moment";
+    toggle: (KeyCode::Char(' '), NONE), "Toggle", "check an option or open a dropdown";
+    open_editor: (KeyCode::Char('e'), KeyModifiers::CONTROL), "Editor", "edit the input in $EDITOR";
+]);
+
+keymap!(
+    /// editing of multi line text, on top of the single line keys
+    TextAreaKeys, KeyContext::TextArea, [
+    newline: (KeyCode::Enter, NONE), "Newline", "break the line at the cursor";
+    submit: (KeyCode::Char('s'), KeyModifiers::CONTROL), "Submit", "submit the text";
+    cursor_up: (KeyCode::Up, NONE), "Up", "move cursor one row up";
+    cursor_down: (KeyCode::Down, NONE), "Down", "move cursor one row down";
+    line_start: (KeyCode::Home, NONE), "Line Start", "move cursor to the start of the row";
+    line_end: (KeyCode::End, NONE), "Line End", "move cursor to the end of the row";
+    select_left: (KeyCode::Left, KeyModifiers::SHIFT), "Select Left", "extend the selection to the left";
+    select_right: (KeyCode::Right, KeyModifiers::SHIFT), "Select Right", "extend the selection to the right";
+    select_up: (KeyCode::Up, KeyModifiers::SHIFT), "Select Up", "extend the selection one row up";
+    select_down: (KeyCode::Down, KeyModifiers::SHIFT), "Select Down", "extend the selection one row down";
+    select_all: (KeyCode::Char('a'), KeyModifiers::CONTROL), "Select All", "select the whole text";
+    undo: (KeyCode::Char('z'), KeyModifiers::CONTROL), "Undo", "undo the last change";
+    redo: (KeyCode::Char('y'), KeyModifiers::CONTROL), "Redo", "redo the last undone change";
+]);
+
+keymap!(
+    /// popups that are not text inputs
+    PopupKeys, KeyContext::Popup, [
+    exit_popup: (KeyCode::Esc, NONE), "Close", "close popup";
+    scroll_up: (KeyCode::Up, NONE), "Scroll Up", "scroll up in popup";
+    scroll_down: (KeyCode::Down, NONE), "Scroll Down", "scroll down in popup";
+    page_up: (KeyCode::PageUp, NONE), "Page Up", "scroll up one page in popup";
+    page_down: (KeyCode::PageDown, NONE), "Page Down", "scroll down one page in popup";
+    move_top: (KeyCode::Home, NONE), "Top", "jump to the first item in popup";
+    move_bottom: (KeyCode::End, NONE), "Bottom", "jump to the last item in popup";
+    select: (KeyCode::Enter, NONE), "Select", "act on the selected item";
+    reset_item: (KeyCode::Char('r'), NONE), "Reset", "reset the selected item to its default";
+]);
@@ -0,0 +1,209 @@
+//TODO: remove once fixed https://github.com/rust-lang/rust-clippy/issues/6818
+#![allow(clippy::use_self)]
+
+use std::{
+    cell::RefCell,
+    fs::{self, File},
+    io::{Read, Write},
+    path::PathBuf,
+    rc::Rc,
+};
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use ron::{
+    self,
+    ser::{PrettyConfig, to_string_pretty},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::args::get_app_config_path;
+
+pub use keymaps::{
+    ActionId, GlobalKeys, KeyBinding, KeyContext, ListKeys, PopupKeys,
+    TabKeys, TextAreaKeys, TextInputKeys,
+};
+
+pub use conflicts::KeyIssue;
+pub use export::KeyExportFormat;
+pub use hints::KeyHintStyle;
+
+mod conflicts;
+mod export;
+mod hints;
+mod keymaps;
+
+pub type SharedKeyConfig = Rc<RefCell<KeyConfig>>;
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyConfig {
+    /// how keys are printed in tabs, command bar and help
+    pub hint_style: KeyHintStyle,
+    pub global: GlobalKeys,
+    pub tab: TabKeys,
+    pub list: ListKeys,
+    pub text_input: TextInputKeys,
+    pub text_area: TextAreaKeys,
+    pub popup: PopupKeys,
+}
+
+impl KeyConfig {
+    pub fn save(&self, file: PathBuf) -> Result<()> {
+        let mut file = File::create(file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    /// every action of every context, in config file order
+    pub fn bindings(&self) -> Vec<KeyBinding> {
+        let mut res = self.global.bindings();
+        res.extend(self.tab.bindings());
+        res.extend(self.list.bindings());
+        res.extend(self.text_input.bindings());
+        res.extend(self.text_area.bindings());
+        res.extend(self.popup.bindings());
+        res
+    }
+
+    /// mutable access to `binding` inside its context
+    pub fn binding_mut(
+        &mut self,
+        binding: &KeyBinding,
+    ) -> Option<&mut KeyEvent> {
+        match binding.context {
+            KeyContext::Global => self.global.get_mut(binding.action),
+            KeyContext::Tab => self.tab.get_mut(binding.action),
+            KeyContext::List => self.list.get_mut(binding.action),
+            KeyContext::TextInput => {
+                self.text_input.get_mut(binding.action)
+            }
+            KeyContext::TextArea => self.text_area.get_mut(binding.action),
+            KeyContext::Popup => self.popup.get_mut(binding.action),
+        }
+    }
+
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("key_config.ron"))
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    /// reads `file` without ever writing to it,
+    /// a missing file means the defaults
+    pub fn read(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file.clone()).map_err(|e| {
+                anyhow::anyhow!("{}: {}", file.to_string_lossy(), e)
+            })
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            match Self::read_file(file.clone()) {
+                Err(e) => {
+                    let config_path = file.clone();
+                    let config_path_old =
+                        format!("{}.old", file.to_string_lossy());
+                    fs::rename(
+                        config_path.clone(),
+                        config_path_old.clone(),
+                    )?;
+
+                    Self::default().save(file)?;
+
+                    Err(anyhow::anyhow!("{}\n Old file was renamed to {:?}.\n Defaults loaded and saved as {:?}",
+                        e,config_path_old,config_path.to_string_lossy()))
+                }
+                Ok(res) => Ok(res),
+            }
+        } else {
+            Self::default().save(file)?;
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{KeyConfig, KeyHintStyle};
+
+    #[test]
+    fn test_get_hint() {
+        let config = KeyConfig::default();
+        let h = config.get_hint(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL,
+        });
+        assert_eq!(h, "^c");
+    }
+
+    #[test]
+    fn test_load_vim_style_example() {
+        assert_eq!(
+            KeyConfig::read_file("vim_style_key_config.ron".into())
+                .is_ok(),
+            true
+        );
+    }
+
+    #[test]
+    fn test_binding_mut() {
+        let mut config = KeyConfig::default();
+        let binding = config.list.bindings()[2];
+        let key = KeyEvent {
+            code: KeyCode::Char('x'),
+            modifiers: KeyModifiers::empty(),
+        };
+
+        *config.binding_mut(&binding).unwrap() = key;
+
+        assert_eq!(binding.path(), "list.remove_line");
+        assert_eq!(config.list.remove_line, key);
+    }
+
+    #[test]
+    fn test_read_leaves_broken_file() {
+        let file = env::temp_dir()
+            .join(format!("key_config_test_{}.ron", process::id()));
+        fs::write(&file, "(global: (quit: nonsense").unwrap();
+
+        assert!(KeyConfig::read(file.clone()).is_err());
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "(global: (quit: nonsense"
+        );
+        assert!(!file.with_extension("ron.old").exists());
+
+        fs::remove_file(&file).unwrap();
+        assert_eq!(KeyConfig::read(file.clone()).unwrap(), KeyConfig::default());
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let config =
+            KeyConfig::read_file("vim_style_key_config.ron".into())
+                .unwrap();
+        let defaults = KeyConfig::default();
+
+        assert_eq!(config.global.quit, defaults.global.quit);
+        assert_ne!(config.list.move_up, defaults.list.move_up);
+        assert_eq!(config.hint_style, KeyHintStyle::Ascii);
+        assert!(config.check_conflicts().is_empty());
+    }
+}
@@ -6,25 +6,37 @@ use log4rs::append::rolling_file::policy::compound::roll::fixed_window::FixedWin
 use log4rs::append::rolling_file::policy::compound::trigger::size::SizeTrigger;
 use log4rs::Config;
 use log4rs::config::{Appender, Root};
+use log4rs::encode::Encode;
 use log4rs::encode::pattern::PatternEncoder;
 use log::LevelFilter;
 
-pub fn init_log() {
+use super::redact::RedactingEncoder;
+
+/// every appender writes through this, it masks the values of `keys`
+fn encoder(keys: &[String]) -> Box<dyn Encode> {
+    Box::new(RedactingEncoder::new(
+        Box::new(PatternEncoder::new("{d} - {m}{n}")),
+        keys,
+    ))
+}
+
+/// `redacted_keys` are the keys whose values get masked
+pub fn init_log(redacted_keys: &[String]) {
     let window_size = 3; // log0, log1, log2
     let fixed_window_roller = FixedWindowRoller::builder().build("log/app-{}.log", window_size).unwrap();
     let size_limit = 100 * 1024 * 1024; // 100M as max log file size to roll
     let size_trigger = SizeTrigger::new(size_limit);
     let compound_policy = CompoundPolicy::new(Box::new(size_trigger), Box::new(fixed_window_roller));
     let rolling_file = RollingFileAppender::builder()
-        .encoder(Box::new(PatternEncoder::new("{d} - {m}{n}")))
+        .encoder(encoder(redacted_keys))
         .build("log/app.log", Box::new(compound_policy)).unwrap();
 
 
     let file_out = FileAppender::builder()
-        .encoder(Box::new(PatternEncoder::new("{d} - {m}{n}")))
+        .encoder(encoder(redacted_keys))
         .build("log/app.log")
         .unwrap();
-    let stdout = ConsoleAppender::builder().build();
+    let stdout = ConsoleAppender::builder().encoder(encoder(redacted_keys)).build();
 
 
     let config = Config::builder()
@@ -40,4 +52,79 @@ pub fn init_log() {
         .unwrap();
 
     let _ = log4rs::init_config(config).unwrap();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, env, fs, process};
+
+    use log::Record;
+    use log4rs::append::{file::FileAppender, Append};
+
+    use super::encoder;
+    use crate::{
+        auth::{Credentials, Secret},
+        settings::Settings,
+    };
+
+    #[test]
+    fn test_no_password_in_log_file() {
+        let file =
+            env::temp_dir().join(format!("redact_test_{}.log", process::id()));
+        let appender = FileAppender::builder()
+            .encoder(encoder(&Settings::default().redacted_keys))
+            .append(false)
+            .build(&file)
+            .unwrap();
+        let credentials = Credentials {
+            username: "ann".to_string(),
+            password: Secret::from("hunter2"),
+        };
+        let password = credentials.password.expose();
+
+        for line in &[
+            format!("{:?}", credentials),
+            format!("login password={}", password),
+            format!(r#"body {{"username":"ann","password":"{}"}}"#, password),
+            format!(
+                "msg is {:?}",
+                [("password", password)].iter().cloned().collect::<HashMap<_, _>>()
+            ),
+        ] {
+            appender
+                .append(&Record::builder().args(format_args!("{}", line)).build())
+                .unwrap();
+        }
+        appender.flush();
+
+        let log = fs::read_to_string(&file).unwrap();
+        fs::remove_file(&file).unwrap();
+        assert_eq!(log.lines().count(), 4);
+        assert!(log.contains("ann"));
+        assert!(!log.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_user_redacted_key() {
+        let file = env::temp_dir()
+            .join(format!("redact_user_test_{}.log", process::id()));
+        let appender = FileAppender::builder()
+            .encoder(encoder(&["api_key".to_string()]))
+            .append(false)
+            .build(&file)
+            .unwrap();
+
+        appender
+            .append(
+                &Record::builder()
+                    .args(format_args!("request api_key=abc123 user=ann"))
+                    .build(),
+            )
+            .unwrap();
+        appender.flush();
+
+        let log = fs::read_to_string(&file).unwrap();
+        fs::remove_file(&file).unwrap();
+        assert!(log.contains("api_key=*** user=ann"));
+    }
+}
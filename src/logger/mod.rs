@@ -1,3 +1,5 @@
 pub use logger::init_log;
+pub use redact::REDACTED_KEYS;
 
 mod logger;
+mod redact;
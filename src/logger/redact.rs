@@ -0,0 +1,124 @@
+use log::Record;
+use log4rs::encode::{writer::simple::SimpleWriter, Encode, Write};
+
+/// what a redacted value gets replaced with
+const MASK: &str = "***";
+
+/// keys whose values never get written to the log,
+/// the default for `redacted_keys` in the settings
+pub const REDACTED_KEYS: &[&str] = &["password", "passwd", "secret", "token"];
+
+/// runs `inner` and masks the values of `keys` in what it produced,
+/// so secrets formatted by accident stay out of the log file
+#[derive(Debug)]
+pub struct RedactingEncoder {
+    inner: Box<dyn Encode>,
+    keys: Vec<String>,
+}
+
+impl RedactingEncoder {
+    pub fn new(inner: Box<dyn Encode>, keys: &[String]) -> Self {
+        Self {
+            inner,
+            keys: keys.iter().map(|k| k.to_ascii_lowercase()).collect(),
+        }
+    }
+}
+
+impl Encode for RedactingEncoder {
+    fn encode(
+        &self,
+        w: &mut dyn Write,
+        record: &Record,
+    ) -> anyhow::Result<()> {
+        let mut buf = SimpleWriter(Vec::new());
+        self.inner.encode(&mut buf, record)?;
+        let line = String::from_utf8_lossy(&buf.0);
+        w.write_all(redact(&line, &self.keys).as_bytes())?;
+        Ok(())
+    }
+}
+
+/// masks the value after any of the lowercase `keys` followed by `:` or `=`,
+/// covers `password=..`, `password: ".."` and `"password":".."`
+pub fn redact(line: &str, keys: &[String]) -> String {
+    let lower = line.to_ascii_lowercase();
+    let bytes = line.as_bytes();
+    let skip = |i: &mut usize, set: &[u8]| {
+        while *i < bytes.len() && set.contains(&bytes[*i]) {
+            *i += 1;
+        }
+    };
+
+    let mut res = String::with_capacity(line.len());
+    let mut copied = 0;
+    let mut pos = 0;
+    while let Some((start, len)) = keys
+        .iter()
+        .filter_map(|k| lower[pos..].find(k.as_str()).map(|i| (pos + i, k.len())))
+        .min()
+    {
+        pos = start + len;
+        let mut i = pos;
+        skip(&mut i, b"\"' ");
+        if i >= bytes.len() || (bytes[i] != b':' && bytes[i] != b'=') {
+            continue;
+        }
+        i += 1;
+        skip(&mut i, b" ");
+
+        let quote = bytes.get(i).copied().filter(|b| *b == b'"' || *b == b'\'');
+        if quote.is_some() {
+            i += 1;
+        }
+        let value_start = i;
+        while i < bytes.len() {
+            match quote {
+                Some(_) if bytes[i] == b'\\' => {
+                    // skips the escaped character, which may be multi byte
+                    i += 1 + line[i + 1..].chars().next().map_or(0, char::len_utf8);
+                }
+                Some(q) if bytes[i] == q || bytes[i] == b'\n' => break,
+                Some(_) => i += 1,
+                None if b" \t\r\n,;})]&\"'".contains(&bytes[i]) => break,
+                None => i += 1,
+            }
+        }
+        let i = i.min(bytes.len());
+
+        if i > value_start {
+            res.push_str(&line[copied..value_start]);
+            res.push_str(MASK);
+            copied = i;
+        }
+        pos = pos.max(i);
+    }
+
+    res.push_str(&line[copied..]);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{redact, REDACTED_KEYS};
+
+    #[test]
+    fn test_redact() {
+        let keys: Vec<String> =
+            REDACTED_KEYS.iter().map(|k| (*k).to_string()).collect();
+
+        assert_eq!(
+            redact("login password=hunter2 user=ann", &keys),
+            "login password=*** user=ann"
+        );
+        assert_eq!(
+            redact(r#"{"username":"ann","Password":"hunt \"er2"}"#, &keys),
+            r#"{"username":"ann","Password":"***"}"#
+        );
+        assert_eq!(
+            redact(r#"Form { token: "äö", password: 1 }"#, &keys),
+            r#"Form { token: "***", password: *** }"#
+        );
+        assert_eq!(redact("password reset failed", &keys), "password reset failed");
+    }
+}
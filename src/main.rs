@@ -1,11 +1,11 @@
 use std::{io, io::stdout, thread};
 use std::error::Error;
 use std::io::Write;
-use std::sync::mpsc;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
-use crossterm::{event::{self},
+use anyhow::{anyhow, Result};
+use crossterm::{event::{self, DisableMouseCapture, EnableMouseCapture},
                 ExecutableCommand,
                 terminal::{
                     disable_raw_mode,
@@ -15,7 +15,7 @@ use crossterm::{event::{self},
 use crossterm::event::{KeyEvent, KeyModifiers};
 use log::{debug, info};
 use scopeguard::defer;
-use tui::backend::{Backend, CrosstermBackend};
+use tui::backend::CrosstermBackend;
 use tui::layout::{Constraint, Direction, Layout};
 use tui::style::{Color, Modifier, Style};
 use tui::Terminal;
@@ -23,18 +23,26 @@ use tui::text::{Span, Spans};
 use tui::widgets::{Block, Borders, Tabs};
 
 use crate::app::App;
-use crate::components::Component;
+use crate::components::{ExternalEditor, MsgKind};
 use crate::keys::KeyConfig;
 use crate::logger::init_log;
+use crate::settings::Settings;
 use crate::ui::style::Theme;
+use crate::ui::UiBackend;
 
 #[allow(dead_code)]
 mod app;
+mod auth;
 mod ui;
 mod keys;
 mod args;
 mod bug_report;
 mod cmdbar;
+mod popup_stack;
+mod queue;
+mod session;
+mod settings;
+mod spinner;
 mod components;
 mod strings;
 mod tabs;
@@ -47,13 +55,47 @@ enum Event<I> {
     Tick,
 }
 
+/// what main tells the input thread
+enum InputControl {
+    /// stop reading the terminal and acknowledge it
+    Pause,
+    Resume,
+}
+
 
 fn main() -> Result<(), Box<dyn Error>> {
-    init_log();
+    args::process_cmdline()?;
+    let settings = Settings::init(Settings::get_config_file()?);
+    // the log masks the keys the settings name, so it starts after them
+    init_log(
+        &settings
+            .as_ref()
+            .map_or_else(|_| Settings::default(), Clone::clone)
+            .redacted_keys,
+    );
     enable_raw_mode()?;
+    // the terminal is in raw mode, problems get shown once the ui is up
+    let mut startup_msgs = Vec::new();
+    let settings = settings.unwrap_or_else(|e| {
+        log::error!("Settings loading error: {}", e);
+        startup_msgs
+            .push((MsgKind::Error, format!("Settings loading error: {}", e)));
+        Settings::default()
+    });
     let key_config = KeyConfig::init(KeyConfig::get_config_file()?)
-        .map_err(|e| eprintln!("KeyConfig loading error: {}", e))
-        .unwrap_or_default();
+        .unwrap_or_else(|e| {
+            log::error!("KeyConfig loading error: {}", e);
+            startup_msgs
+                .push((MsgKind::Error, format!("KeyConfig loading error: {}", e)));
+            KeyConfig::default()
+        });
+    let conflicts = key_config.check_conflicts().lines(&key_config);
+    for line in &conflicts {
+        log::warn!("key config: {}", line);
+    }
+    if !conflicts.is_empty() {
+        startup_msgs.push((MsgKind::Warning, conflicts.join("\n")));
+    }
     // let theme = Theme::init(cliargs.theme)
     //     .map_err(|e| eprintln!("Theme loading error: {}", e))
     //     .unwrap_or_default();
@@ -69,11 +111,27 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     let (tx, rx) = mpsc::channel();
+    let (control_tx, control_rx) = mpsc::channel();
+    let (paused_tx, paused_rx) = mpsc::channel();
 
     let tick_rate = Duration::from_millis(200);
     thread::spawn(move || {
         let mut last_tick = Instant::now();
         loop {
+            if let Ok(InputControl::Pause) = control_rx.try_recv() {
+                // nothing gets read until main took the terminal back
+                if paused_tx.send(()).is_err() {
+                    return;
+                }
+                loop {
+                    match control_rx.recv() {
+                        Ok(InputControl::Resume) => break,
+                        Ok(InputControl::Pause) => (),
+                        Err(_) => return,
+                    }
+                }
+                last_tick = Instant::now();
+            }
             // poll for tick rate duration, if no events, sent tick event.
             let timeout = tick_rate
                 .checked_sub(last_tick.elapsed())
@@ -91,7 +149,10 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     });
 
-    let mut app = App::new(false, theme, key_config);
+    let mut app = App::new(false, theme, key_config, settings)?;
+    for (kind, msg) in startup_msgs {
+        app.show_msg(kind, msg);
+    }
 
     terminal.clear()?;
 
@@ -102,7 +163,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         // app.cmdbar.borrow_mut().refresh_width(fsize.width);
         match rx.recv()? {
             Event::Input(event) => {
-                app.event(event)?;
+                app.event(event);
             }
 
             Event::Tick => {
@@ -111,6 +172,11 @@ fn main() -> Result<(), Box<dyn Error>> {
             }
         }
 
+        if let Some(text) = app.take_editor_request() {
+            let edited = run_editor(&text, &control_tx, &paused_rx);
+            app.edited(edited);
+        }
+
         if app.is_quit() {
             break;
         }
@@ -119,30 +185,57 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn draw<B: Backend>(
-    terminal: &mut Terminal<B>,
+fn draw(
+    terminal: &mut Terminal<UiBackend>,
     app: &App,
 ) -> io::Result<()> {
     if app.requires_redraw() {
         terminal.resize(terminal.size()?)?;
     }
 
-    terminal.draw(|mut f| {
-        if let Err(e) = app.draw(&mut f) {
-            log::error!("failed to draw: {:?}", e);
-        }
-    })?;
+    terminal.draw(|mut f| app.draw(&mut f))?;
 
     Ok(())
 }
 
+/// pauses the input thread, leaves the terminal to the external editor
+/// and takes both back whatever the editor did
+fn run_editor(
+    text: &str,
+    control: &Sender<InputControl>,
+    paused: &Receiver<()>,
+) -> Result<String> {
+    control
+        .send(InputControl::Pause)
+        .map_err(|_| anyhow!("input thread stopped"))?;
+    // the input thread may be waiting in a poll, the editor waits for it
+    paused.recv()?;
+
+    shutdown_terminal();
+    let edited = ExternalEditor::edit(text);
+    let restored = setup_terminal();
+
+    control
+        .send(InputControl::Resume)
+        .map_err(|_| anyhow!("input thread stopped"))?;
+    restored?;
+    edited
+}
+
 fn setup_terminal() -> Result<()> {
     enable_raw_mode()?;
     io::stdout().execute(EnterAlternateScreen)?;
+    io::stdout().execute(EnableMouseCapture)?;
     Ok(())
 }
 
 fn shutdown_terminal() {
+    let leave_mouse = io::stdout().execute(DisableMouseCapture).map(|_f| ());
+
+    if let Err(e) = leave_mouse {
+        eprintln!("leave_mouse failed:\n{}", e);
+    }
+
     let leave_screen =
         io::stdout().execute(LeaveAlternateScreen).map(|_f| ());
 
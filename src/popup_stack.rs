@@ -0,0 +1,76 @@
+/// names the popups that are open, the last one is on top,
+/// each with whatever had the focus when it opened
+pub struct PopupStack<F> {
+    open: Vec<(&'static str, F)>,
+}
+
+impl<F> Default for PopupStack<F> {
+    fn default() -> Self {
+        Self { open: Vec::new() }
+    }
+}
+
+impl<F: Clone> PopupStack<F> {
+    /// drops popups that got hidden and puts newly shown ones on top,
+    /// in the order they appear in `visible`, new ones remember `focused`,
+    /// returns the dropped ones bottom first with the focus to give back
+    pub fn sync(
+        &mut self,
+        visible: &[&'static str],
+        focused: &F,
+    ) -> Vec<(&'static str, F)> {
+        let (open, closed) = self
+            .open
+            .drain(..)
+            .partition(|(p, _)| visible.contains(p));
+        self.open = open;
+        for p in visible {
+            if !self.open.iter().any(|(o, _)| o == p) {
+                self.open.push((p, focused.clone()));
+            }
+        }
+        closed
+    }
+
+    pub fn top(&self) -> Option<&'static str> {
+        self.open.last().map(|(p, _)| *p)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.open.is_empty()
+    }
+
+    /// bottom to top
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.open.iter().map(|(p, _)| *p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PopupStack;
+
+    #[test]
+    fn test_sync_keeps_stacking_order() {
+        let mut stack = PopupStack::default();
+
+        stack.sync(&["login"], &());
+        stack.sync(&["help", "login"], &());
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec!["login", "help"]);
+
+        stack.sync(&["login"], &());
+        assert_eq!(stack.top(), Some("login"));
+        stack.sync(&[], &());
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_sync_gives_focus_back() {
+        let mut stack = PopupStack::default();
+
+        assert!(stack.sync(&["login"], &"list").is_empty());
+        stack.sync(&["login", "help"], &"login");
+        assert_eq!(stack.sync(&["login"], &"login"), vec![("help", "login")]);
+        assert_eq!(stack.sync(&[], &"login"), vec![("login", "list")]);
+    }
+}
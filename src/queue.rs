@@ -0,0 +1,49 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use crate::{auth::Identity, components::MsgKind};
+
+/// actions that ask the user before they run
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// removes line `index` of the list titled `list`
+    RemoveLine { list: String, index: usize },
+    /// empties the list titled `list`
+    RemoveAllLines { list: String },
+}
+
+/// requests components leave for the app
+pub enum InternalEvent {
+    /// queues a message in the message popup
+    ShowMsg(MsgKind, String),
+    /// shows a notification that goes away on its own
+    Toast(MsgKind, String),
+    /// the login dialog accepted these credentials
+    LoggedIn(Identity),
+    /// shows the confirm dialog for the action
+    ConfirmAction(Action),
+    /// the user agreed, whoever owns the action runs it now
+    ConfirmedAction(Action),
+    /// an input wants this text changed in the external editor,
+    /// what comes back goes to `Component::edited`
+    OpenExternalEditor(String),
+}
+
+/// single threaded simple queue for components to communicate with each other
+#[derive(Clone, Default)]
+pub struct Queue {
+    data: Rc<RefCell<VecDeque<InternalEvent>>>,
+}
+
+impl Queue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, ev: InternalEvent) {
+        self.data.borrow_mut().push_back(ev);
+    }
+
+    pub fn pop(&self) -> Option<InternalEvent> {
+        self.data.borrow_mut().pop_front()
+    }
+}
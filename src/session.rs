@@ -0,0 +1,111 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::Identity;
+
+/// when an idle session locks the app again
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// minutes without input before the login dialog comes back, `0` never
+    pub idle_timeout_mins: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout_mins: 15,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// huge values saturate, they mean never in practice
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        if self.idle_timeout_mins == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(
+                self.idle_timeout_mins.saturating_mul(60),
+            ))
+        }
+    }
+}
+
+/// the logged in user, held by the app until logout or timeout
+pub struct Session {
+    identity: Identity,
+    login_time: SystemTime,
+    last_input: Instant,
+}
+
+impl Session {
+    pub fn new(identity: Identity) -> Self {
+        Self {
+            identity,
+            login_time: SystemTime::now(),
+            last_input: Instant::now(),
+        }
+    }
+
+    pub const fn identity(&self) -> &Identity {
+        &self.identity
+    }
+
+    /// minutes since the login, for display
+    pub fn minutes(&self) -> u64 {
+        self.login_time
+            .elapsed()
+            .map(|d| d.as_secs() / 60)
+            .unwrap_or_default()
+    }
+
+    /// the user did something, the idle timeout starts over
+    pub fn touch(&mut self) {
+        self.last_input = Instant::now();
+    }
+
+    /// `true` once there was no input for longer than the configured timeout
+    pub fn is_idle(&self, config: &SessionConfig, now: Instant) -> bool {
+        config
+            .idle_timeout()
+            .map(|timeout| now.saturating_duration_since(self.last_input) > timeout)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{Session, SessionConfig};
+    use crate::auth::Identity;
+
+    #[test]
+    fn test_idle_timeout() {
+        let mut session = Session::new(Identity::new("ann"));
+        let config = SessionConfig {
+            idle_timeout_mins: 1,
+        };
+        let later = Instant::now() + Duration::from_secs(61);
+
+        assert!(!session.is_idle(&config, Instant::now()));
+        assert!(session.is_idle(&config, later));
+        assert!(!session.is_idle(
+            &SessionConfig {
+                idle_timeout_mins: 0
+            },
+            later
+        ));
+
+        session.touch();
+        assert!(!session.is_idle(&config, Instant::now()));
+
+        let huge = SessionConfig {
+            idle_timeout_mins: u64::MAX,
+        };
+        assert_eq!(huge.idle_timeout(), Some(Duration::from_secs(u64::MAX)));
+        assert!(!session.is_idle(&huge, later));
+    }
+}
@@ -0,0 +1,85 @@
+use std::{fs::File, io::Read, path::PathBuf, rc::Rc};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    args::get_app_config_path,
+    components::{TextAreaConfig, ToastConfig},
+    logger::REDACTED_KEYS,
+    session::SessionConfig,
+};
+
+pub type SharedSettings = Rc<Settings>;
+
+/// everything configurable that is not a key binding,
+/// read from `settings.ron`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// lines the expanded command bar may grow to
+    pub cmd_bar_max_lines: u16,
+    /// ask before lines get removed from a list
+    pub confirm_removal: bool,
+    pub toasts: ToastConfig,
+    pub session: SessionConfig,
+    pub text_area: TextAreaConfig,
+    /// keys whose values get masked in the log, replaces the defaults
+    pub redacted_keys: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            cmd_bar_max_lines: 4,
+            confirm_removal: true,
+            toasts: ToastConfig::default(),
+            session: SessionConfig::default(),
+            text_area: TextAreaConfig::default(),
+            redacted_keys: REDACTED_KEYS
+                .iter()
+                .map(|k| (*k).to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("settings.ron"))
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    /// a missing file means the defaults, nothing gets written
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file.clone()).map_err(|e| {
+                anyhow!("{}: {}", file.to_string_lossy(), e)
+            })
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+
+    #[test]
+    fn test_settings_example() {
+        let settings =
+            Settings::read_file("settings_example.ron".into()).unwrap();
+
+        assert_eq!(settings.session.idle_timeout_mins, 30);
+        assert_eq!(settings.toasts, Settings::default().toasts);
+        assert!(settings.redacted_keys.contains(&"api_key".to_string()));
+    }
+}
@@ -0,0 +1,17 @@
+static SPINNER_CHARS: &[char] = &['⣷', '⣯', '⣟', '⡿', '⢿', '⣻', '⣽', '⣾'];
+
+/// shows that something runs in the background, advanced on tick
+#[derive(Default)]
+pub struct Spinner {
+    idx: usize,
+}
+
+impl Spinner {
+    pub fn update(&mut self) {
+        self.idx = (self.idx + 1) % SPINNER_CHARS.len();
+    }
+
+    pub fn get(&self) -> char {
+        SPINNER_CHARS[self.idx]
+    }
+}
@@ -1,4 +1,6 @@
-use crate::keys::SharedKeyConfig;
+use crossterm::event::KeyEvent;
+
+use crate::{components::MsgKind, keys::SharedKeyConfig, version::Version};
 
 pub mod order {
     pub static NAV: i8 = 2;
@@ -40,60 +42,249 @@ pub fn title_index(_key_config: &SharedKeyConfig) -> String {
     "Staged Changes".to_string()
 }
 
-pub fn tab_t01(key_config: &SharedKeyConfig) -> String {
-    format!("T01 [{}]", key_config.get_hint(key_config.tab_status))
+pub fn tab_t01(_key_config: &SharedKeyConfig) -> String {
+    "T01".to_string()
 }
 
-pub fn tab_t02(key_config: &SharedKeyConfig) -> String {
-    format!("T02 [{}]", key_config.get_hint(key_config.tab_log))
+pub fn tab_t02(_key_config: &SharedKeyConfig) -> String {
+    "T02".to_string()
 }
 
-pub fn tab_t03(key_config: &SharedKeyConfig) -> String {
-    format!("T03 [{}]", key_config.get_hint(key_config.tab_files))
+pub fn tab_t03(_key_config: &SharedKeyConfig) -> String {
+    "T03".to_string()
 }
 
-pub fn tab_status(key_config: &SharedKeyConfig) -> String {
-    format!("Status [{}]", key_config.get_hint(key_config.tab_status))
+pub fn tab_title(
+    key_config: &SharedKeyConfig,
+    icon: Option<&str>,
+    title: &str,
+    hotkey: Option<KeyEvent>,
+) -> String {
+    let mut res = String::new();
+    if let Some(icon) = icon {
+        res.push_str(icon);
+        res.push(' ');
+    }
+    res.push_str(title);
+    if let Some(key) = hotkey {
+        res.push_str(&format!(" [{}]", key_config.borrow().get_hint(key)));
+    }
+    res
 }
 
-pub fn tab_log(key_config: &SharedKeyConfig) -> String {
-    format!("Log [{}]", key_config.get_hint(key_config.tab_log))
+pub fn tab_divider(_key_config: &SharedKeyConfig) -> String {
+    " | ".to_string()
 }
 
-pub fn tab_files(key_config: &SharedKeyConfig) -> String {
-    format!("Files [{}]", key_config.get_hint(key_config.tab_files))
+pub static DISABLED_NOT_FOCUSED: &str = "list is not focused";
+pub static DISABLED_NOTHING_SELECTED: &str = "nothing selected";
+pub static DISABLED_NO_MATCH: &str = "no command matches";
+pub static DISABLED_CAPTURING: &str = "waiting for a key";
+
+pub fn cmd_splitter(_key_config: &SharedKeyConfig) -> String {
+    " ".to_string()
 }
 
-pub fn tab_stashing(key_config: &SharedKeyConfig) -> String {
+pub fn cmd_more(key_config: &SharedKeyConfig, expanded: bool) -> String {
     format!(
-        "Stashing [{}]",
-        key_config.get_hint(key_config.tab_stashing)
+        "{} [{}]",
+        if expanded { "less" } else { "more" },
+        key_config.borrow().get_hint(key_config.borrow().global.cmd_bar_toggle)
     )
 }
 
-pub fn tab_stashes(key_config: &SharedKeyConfig) -> String {
-    format!(
-        "Stashes [{}]",
-        key_config.get_hint(key_config.tab_stashes)
-    )
+pub fn cmd_disabled(
+    _key_config: &SharedKeyConfig,
+    name: &str,
+    reason: &str,
+) -> String {
+    format!("{} ({})", name, reason)
 }
 
-pub fn tab_divider(_key_config: &SharedKeyConfig) -> String {
-    " | ".to_string()
+pub fn msg_logged_in(_key_config: &SharedKeyConfig, name: &str) -> String {
+    format!("logged in as '{}'", name)
 }
 
-pub fn cmd_splitter(_key_config: &SharedKeyConfig) -> String {
-    " ".to_string()
+pub fn msg_logged_out(_key_config: &SharedKeyConfig) -> String {
+    "logged out".to_string()
+}
+
+pub fn msg_session_locked(_key_config: &SharedKeyConfig, mins: u64) -> String {
+    format!("no input for {} minutes, please log in again", mins)
+}
+
+/// the logged in user as shown next to the tabs
+pub fn session_user(
+    _key_config: &SharedKeyConfig,
+    name: &str,
+    roles: &[String],
+    minutes: u64,
+) -> String {
+    let mut res = name.to_string();
+    if !roles.is_empty() {
+        res.push_str(&format!(" ({})", roles.join(", ")));
+    }
+    res.push_str(&format!(" {}m", minutes));
+    res
+}
+
+pub fn login_checking(_key_config: &SharedKeyConfig) -> String {
+    "checking credentials...".to_string()
+}
+
+pub fn login_failed(_key_config: &SharedKeyConfig) -> String {
+    "wrong username or password".to_string()
+}
+
+pub fn login_error(_key_config: &SharedKeyConfig, error: &str) -> String {
+    format!("login failed: {}", error)
+}
+
+pub fn login_caps_hint(_key_config: &SharedKeyConfig) -> String {
+    "all upper case, is caps lock on?".to_string()
+}
+
+pub fn login_locked(_key_config: &SharedKeyConfig, secs: u64) -> String {
+    format!("too many wrong attempts, wait {}s", secs)
+}
+
+pub fn login_remember(_key_config: &SharedKeyConfig) -> String {
+    "remember username".to_string()
+}
+
+pub fn login_submit(_key_config: &SharedKeyConfig) -> String {
+    "Login".to_string()
+}
+
+pub fn form_submit(_key_config: &SharedKeyConfig) -> String {
+    "Submit".to_string()
+}
+
+pub fn form_required(_key_config: &SharedKeyConfig, label: &str) -> String {
+    format!("{} is required", label)
+}
+
+pub fn form_not_a_number(_key_config: &SharedKeyConfig) -> String {
+    "not a whole number".to_string()
+}
+
+pub fn form_out_of_range(
+    _key_config: &SharedKeyConfig,
+    min: i64,
+    max: i64,
+) -> String {
+    format!("must be between {} and {}", min, max)
 }
 
-pub fn msg_opening_editor(_key_config: &SharedKeyConfig) -> String {
-    "opening editor...".to_string()
+pub fn form_revealed(_key_config: &SharedKeyConfig, label: &str) -> String {
+    format!("{} (shown)", label)
+}
+
+pub fn text_area_counter(
+    _key_config: &SharedKeyConfig,
+    chars: usize,
+    lines: usize,
+) -> String {
+    format!("({} chars, {} lines)", chars, lines)
+}
+
+pub fn message_title(_key_config: &SharedKeyConfig) -> String {
+    "Message".to_string()
+}
+
+pub fn entry_title(_key_config: &SharedKeyConfig) -> String {
+    "Entry".to_string()
+}
+
+pub fn entry_too_long(_key_config: &SharedKeyConfig, max: usize) -> String {
+    format!("at most {} characters", max)
+}
+
+pub fn dropdown_no_match(_key_config: &SharedKeyConfig) -> String {
+    "no option matches".to_string()
+}
+
+pub fn login_no_backend(_key_config: &SharedKeyConfig) -> String {
+    "no authentication backend configured".to_string()
 }
 
 pub fn msg_title_error(_key_config: &SharedKeyConfig) -> String {
     "Error".to_string()
 }
 
+pub fn msg_title_warning(_key_config: &SharedKeyConfig) -> String {
+    "Warning".to_string()
+}
+
+pub fn msg_title_info(_key_config: &SharedKeyConfig) -> String {
+    "Info".to_string()
+}
+
+pub fn msg_title_success(_key_config: &SharedKeyConfig) -> String {
+    "Success".to_string()
+}
+
+pub fn notifications_title(_key_config: &SharedKeyConfig) -> String {
+    "Notifications".to_string()
+}
+
+pub fn notifications_empty(_key_config: &SharedKeyConfig) -> String {
+    "nothing happened yet".to_string()
+}
+
+/// marks the kind of a notification in the history
+pub fn notification_prefix(
+    key_config: &SharedKeyConfig,
+    kind: MsgKind,
+) -> String {
+    format!("[{}] ", msg_title(key_config, kind, 0))
+}
+
+pub fn toast_line_removed(
+    _key_config: &SharedKeyConfig,
+    list: &str,
+    index: usize,
+) -> String {
+    format!("removed line {} from '{}'", index + 1, list)
+}
+
+pub fn toast_lines_cleared(
+    _key_config: &SharedKeyConfig,
+    list: &str,
+) -> String {
+    format!("removed every line from '{}'", list)
+}
+
+pub fn toast_keys_applied(_key_config: &SharedKeyConfig) -> String {
+    "key bindings applied".to_string()
+}
+
+pub fn toast_keys_not_saved(
+    _key_config: &SharedKeyConfig,
+    error: &anyhow::Error,
+) -> String {
+    format!("key bindings not saved: {}", error)
+}
+
+/// title of the message popup, `waiting` messages come after this one
+pub fn msg_title(
+    key_config: &SharedKeyConfig,
+    kind: MsgKind,
+    waiting: usize,
+) -> String {
+    let title = match kind {
+        MsgKind::Error => msg_title_error(key_config),
+        MsgKind::Warning => msg_title_warning(key_config),
+        MsgKind::Info => msg_title_info(key_config),
+        MsgKind::Success => msg_title_success(key_config),
+    };
+    if waiting > 0 {
+        format!("{} (+{} more)", title, waiting)
+    } else {
+        title
+    }
+}
+
 pub fn commit_title() -> String {
     "Commit".to_string()
 }
@@ -273,6 +464,76 @@ pub fn help_title(_key_config: &SharedKeyConfig) -> String {
     "Help: all commands".to_string()
 }
 
+pub fn help_filter(_key_config: &SharedKeyConfig, filter: &str) -> String {
+    format!("filter: {}", filter)
+}
+
+pub fn help_scopes(
+    _key_config: &SharedKeyConfig,
+    group: &str,
+    scopes: &str,
+) -> String {
+    format!("{} key, used in: {}", group, scopes)
+}
+
+pub fn help_footer(_key_config: &SharedKeyConfig) -> String {
+    format!("{} {}", env!("CARGO_PKG_NAME"), Version::new())
+}
+
+pub fn confirm_title_remove_line(_key_config: &SharedKeyConfig) -> String {
+    "Remove Line".to_string()
+}
+
+pub fn confirm_msg_remove_line(
+    _key_config: &SharedKeyConfig,
+    list: &str,
+    index: usize,
+) -> String {
+    format!("Remove line {} from '{}' ?", index + 1, list)
+}
+
+pub fn confirm_title_remove_all(_key_config: &SharedKeyConfig) -> String {
+    "Remove All".to_string()
+}
+
+pub fn confirm_msg_remove_all(
+    _key_config: &SharedKeyConfig,
+    list: &str,
+) -> String {
+    format!("Remove every line from '{}' ?", list)
+}
+
+pub fn confirm_yes(_key_config: &SharedKeyConfig) -> String {
+    "[ Yes ]".to_string()
+}
+
+pub fn confirm_no(_key_config: &SharedKeyConfig) -> String {
+    "[ No ]".to_string()
+}
+
+pub fn palette_title(_key_config: &SharedKeyConfig) -> String {
+    "Commands".to_string()
+}
+
+pub fn keys_editor_title(_key_config: &SharedKeyConfig) -> String {
+    "Key Bindings".to_string()
+}
+
+pub fn keys_editor_capture(
+    _key_config: &SharedKeyConfig,
+    title: &str,
+) -> String {
+    format!("press the new key for '{}'", title)
+}
+
+pub fn keys_editor_saved(_key_config: &SharedKeyConfig) -> String {
+    "saved, applied when this popup closes".to_string()
+}
+
+pub fn keys_editor_unsaved(_key_config: &SharedKeyConfig) -> String {
+    "not saved, applied when this popup closes".to_string()
+}
+
 pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
     "Files to Stash".to_string()
 }
@@ -372,234 +633,139 @@ pub mod commit {
 }
 
 pub mod commands {
+    use crossterm::event::KeyEvent;
+
     use crate::components::CommandText;
-    use crate::keys::SharedKeyConfig;
+    use crate::keys::{ActionId, KeyContext, SharedKeyConfig};
 
     static CMD_GROUP_GENERAL: &str = "-- General --";
-    static CMD_GROUP_DIFF: &str = "-- Diff --";
-    static CMD_GROUP_CHANGES: &str = "-- Changes --";
-    static CMD_GROUP_COMMIT: &str = "-- Commit --";
-    static CMD_GROUP_STASHING: &str = "-- Stashing --";
-    static CMD_GROUP_STASHES: &str = "-- Stashes --";
-    static CMD_GROUP_LOG: &str = "-- Log --";
-    static CMD_GROUP_BRANCHES: &str = "-- Branches --";
-
-    pub fn search_input_enable(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Search [{}]",
-                key_config.get_hint(key_config.stashing_save)
-            ),
-            "Enable Search input",
-            CMD_GROUP_GENERAL,
-        )
-    }
+    static CMD_GROUP_LIST: &str = "-- List --";
+    static CMD_GROUP_INPUT: &str = "-- Input --";
 
     pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
                 "Next [{}]",
-                key_config.get_hint(key_config.tab_toggle)
+                key_config.borrow().get_hint(key_config.borrow().global.tab_toggle)
             ),
             "switch to next tab",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "tab_toggle"))
     }
 
-    pub fn toggle_tabs_direct(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn toggle_tabs_reverse(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Tab [{}{}{}{}{}]",
-                key_config.get_hint(key_config.tab_status),
-                key_config.get_hint(key_config.tab_log),
-                key_config.get_hint(key_config.tab_files),
-                key_config.get_hint(key_config.tab_stashing),
-                key_config.get_hint(key_config.tab_stashes),
+                "Previous [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.tab_toggle_reverse)
             ),
-            "switch top level tabs directly",
+            "switch to previous tab",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "tab_toggle_reverse"))
     }
 
-    pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Help [{}]",
-                key_config.get_hint(key_config.open_help)
-            ),
-            "open this help screen",
-            CMD_GROUP_GENERAL,
-        )
-    }
-
-    pub fn navigate_commit_message(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn tab_move(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Nav [{}{}]",
-                key_config.get_hint(key_config.move_up),
-                key_config.get_hint(key_config.move_down)
+                "Move Tab [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.tab_move_left),
+                key_config.borrow().get_hint(key_config.borrow().global.tab_move_right)
             ),
-            "navigate commit message",
+            "move the current tab left or right",
             CMD_GROUP_GENERAL,
         )
     }
 
-    pub fn navigate_tree(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn tab_hide(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Nav [{}{}{}{}]",
-                key_config.get_hint(key_config.move_up),
-                key_config.get_hint(key_config.move_down),
-                key_config.get_hint(key_config.move_right),
-                key_config.get_hint(key_config.move_left)
+                "Hide Tab [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.tab_hide)
             ),
-            "navigate tree view, collapse, expand",
+            "hide the current tab",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "tab_hide"))
     }
 
-    pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn tab_show_all(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Scroll [{}{}]",
-                key_config.get_hint(key_config.focus_above),
-                key_config.get_hint(key_config.focus_below)
+                "Show Tabs [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.tab_show_all)
             ),
-            "scroll up or down in focused view",
+            "show all hidden tabs again",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "tab_show_all"))
     }
 
-    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Copy [{}]",
-                key_config.get_hint(key_config.copy),
-            ),
-            "copy selected lines to clipboard",
-            CMD_GROUP_DIFF,
-        )
-    }
-
-    pub fn copy_hash(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Copy Hash [{}]",
-                key_config.get_hint(key_config.copy),
-            ),
-            "copy selected commit hash to clipboard",
-            CMD_GROUP_LOG,
-        )
-    }
-
-    pub fn push_tags(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Push Tags [{}]",
-                key_config.get_hint(key_config.push),
-            ),
-            "push tags to remote",
-            CMD_GROUP_LOG,
-        )
-    }
-
-    pub fn diff_home_end(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Jump up/down [{},{},{},{}]",
-                key_config.get_hint(key_config.home),
-                key_config.get_hint(key_config.end),
-                key_config.get_hint(key_config.move_up),
-                key_config.get_hint(key_config.move_down)
-            ),
-            "scroll to top or bottom of diff",
-            CMD_GROUP_DIFF,
-        )
-    }
-
-    pub fn diff_hunk_add(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Add hunk [{}]",
-                key_config.get_hint(key_config.enter),
-            ),
-            "adds selected hunk to stage",
-            CMD_GROUP_DIFF,
-        )
-    }
-
-    pub fn diff_hunk_revert(
+    pub fn toggle_tabs_direct(
         key_config: &SharedKeyConfig,
+        hotkeys: &[KeyEvent],
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Reset hunk [{}]",
-                key_config.get_hint(key_config.status_reset_item),
+                "Tab [{}]",
+                hotkeys
+                    .iter()
+                    .map(|k| key_config.borrow().get_hint(*k))
+                    .collect::<String>()
             ),
-            "reverts selected hunk",
-            CMD_GROUP_DIFF,
+            "switch top level tabs directly",
+            CMD_GROUP_GENERAL,
         )
     }
 
-    pub fn diff_lines_revert(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Reset lines [{}]",
-                key_config.get_hint(key_config.diff_reset_lines),
+                "Help [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.open_help)
             ),
-            "resets selected lines",
-            CMD_GROUP_DIFF,
+            "open this help screen",
+            CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "open_help"))
     }
 
-    pub fn diff_lines_stage(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Stage lines [{}]",
-                key_config.get_hint(key_config.diff_stage_lines),
+                "Quit [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.quit)
             ),
-            "stage selected lines",
-            CMD_GROUP_DIFF,
+            "quit application",
+            CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "quit"))
     }
 
-    pub fn diff_lines_unstage(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Unstage lines [{}]",
-                key_config.get_hint(key_config.diff_stage_lines),
+                "Scroll [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.scroll_up),
+                key_config.borrow().get_hint(key_config.borrow().popup.scroll_down)
             ),
-            "unstage selected lines",
-            CMD_GROUP_DIFF,
+            "scroll up or down in focused view",
+            CMD_GROUP_GENERAL,
         )
     }
 
-    pub fn diff_hunk_remove(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn scroll_page(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Remove hunk [{}]",
-                key_config.get_hint(key_config.enter),
+                "Page [{}{}{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.page_up),
+                key_config.borrow().get_hint(key_config.borrow().popup.page_down),
+                key_config.borrow().get_hint(key_config.borrow().popup.move_top),
+                key_config.borrow().get_hint(key_config.borrow().popup.move_bottom)
             ),
-            "removes selected hunk from stage",
-            CMD_GROUP_DIFF,
+            "scroll by page or jump to top or bottom",
+            CMD_GROUP_GENERAL,
         )
     }
 
@@ -607,644 +773,508 @@ pub mod commands {
         CommandText::new(
             format!(
                 "Close [{}]",
-                key_config.get_hint(key_config.exit_popup),
+                key_config.borrow().get_hint(key_config.borrow().popup.exit_popup)
             ),
             "close overlay (e.g commit, help)",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Popup, "exit_popup"))
     }
 
-    pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Close [{}]",
-                key_config.get_hint(key_config.enter),
-            ),
-            "close msg popup (e.g msg)",
-            CMD_GROUP_GENERAL,
-        )
-            .hide_help()
-    }
-
-    pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn msg_dismiss(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Validate [{}]",
-                key_config.get_hint(key_config.enter),
+                "Dismiss [{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.exit_popup)
             ),
-            "validate msg",
+            "close the message and show the next one",
             CMD_GROUP_GENERAL,
         )
-            .hide_help()
+        .action(ActionId::new(KeyContext::Popup, "exit_popup"))
     }
 
-    pub fn select_status(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn confirm_choose(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "To files [{},{}]",
-                key_config.get_hint(key_config.tab_status),
-                key_config.get_hint(key_config.tab_log),
+                "Choose [{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.select)
             ),
-            "focus/select file tree of staged or unstaged files",
+            "run the action or keep things as they are",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Popup, "select"))
     }
 
-    pub fn abort_merge(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn confirm_switch(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Abort merge [{}]",
-                key_config.get_hint(key_config.abort_merge),
+                "Yes/No [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.cursor_left),
+                key_config.borrow().get_hint(key_config.borrow().text_input.cursor_right)
             ),
-            "abort ongoing merge",
+            "switch between Yes and No",
             CMD_GROUP_GENERAL,
         )
     }
 
-    pub fn select_staging(
+    pub fn notifications_open(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "To stage [{}]",
-                key_config.get_hint(key_config.toggle_workarea),
+                "Notifications [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.open_notifications)
             ),
-            "focus/select staging area",
+            "list the notifications shown so far",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "open_notifications"))
     }
 
-    pub fn select_unstaged(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn logout(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "To unstaged [{}]",
-                key_config.get_hint(key_config.toggle_workarea),
+                "Logout [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.logout)
             ),
-            "focus/select unstaged area",
+            "end the session of the logged in user",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "logout"))
     }
 
-    pub fn undo_commit(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn palette_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Undo Commit [{}]",
-                key_config.get_hint(key_config.undo_commit),
+                "Commands [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.open_palette)
             ),
-            "undo last commit",
+            "search and run a command",
             CMD_GROUP_GENERAL,
         )
     }
 
-    pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn palette_run(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Commit [{}]",
-                key_config.get_hint(key_config.open_commit),
+                "Run [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.confirm)
             ),
-            "open commit popup (available in non-empty stage)",
-            CMD_GROUP_COMMIT,
+            "run the selected command",
+            CMD_GROUP_GENERAL,
         )
     }
 
-    pub fn commit_open_editor(
+    pub fn keys_editor_open(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Open editor [{}]",
-                key_config.get_hint(key_config.open_commit_editor),
-            ),
-            "open commit editor (available in commit popup)",
-            CMD_GROUP_COMMIT,
-        )
-    }
-
-    pub fn commit_enter(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Commit [{}]",
-                key_config.get_hint(key_config.enter),
-            ),
-            "commit (available when commit message is non-empty)",
-            CMD_GROUP_COMMIT,
-        )
-            .hide_help()
-    }
-
-    pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Amend [{}]",
-                key_config.get_hint(key_config.commit_amend),
-            ),
-            "amend last commit (available in commit popup)",
-            CMD_GROUP_COMMIT,
-        )
-    }
-
-    pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Edit [{}]",
-                key_config.get_hint(key_config.edit_file),
-            ),
-            "edit the currently selected file in an external editor",
-            CMD_GROUP_CHANGES,
-        )
-    }
-
-    pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Stage [{}]",
-                key_config.get_hint(key_config.enter),
-            ),
-            "stage currently selected file or entire path",
-            CMD_GROUP_CHANGES,
-        )
-    }
-
-    pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Stage All [{}]",
-                key_config.get_hint(key_config.status_stage_all),
-            ),
-            "stage all changes (in unstaged files)",
-            CMD_GROUP_CHANGES,
-        )
-    }
-
-    pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Unstage [{}]",
-                key_config.get_hint(key_config.enter),
-            ),
-            "unstage currently selected file or entire path",
-            CMD_GROUP_CHANGES,
-        )
-    }
-
-    pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Unstage all [{}]",
-                key_config.get_hint(key_config.status_stage_all),
-            ),
-            "unstage all files (in staged files)",
-            CMD_GROUP_CHANGES,
-        )
-    }
-
-    pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Reset [{}]",
-                key_config.get_hint(key_config.status_reset_item),
-            ),
-            "revert changes in selected file or entire path",
-            CMD_GROUP_CHANGES,
-        )
-    }
-
-    pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Ignore [{}]",
-                key_config.get_hint(key_config.status_ignore_file),
+                "Keys [{}]",
+                key_config.borrow().get_hint(key_config.borrow().global.open_key_editor)
             ),
-            "Add file or path to .gitignore",
-            CMD_GROUP_CHANGES,
+            "edit key bindings",
+            CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Global, "open_key_editor"))
     }
 
-    pub fn diff_focus_left(
+    pub fn keys_editor_rebind(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Back [{}]",
-                key_config.get_hint(key_config.focus_left),
+                "Rebind [{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.select)
             ),
-            "view and select changed files",
+            "capture a new key for the selected action",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Popup, "select"))
     }
 
-    pub fn diff_focus_right(
+    pub fn keys_editor_reset(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Diff [{}]",
-                key_config.get_hint(key_config.focus_right),
+                "Reset [{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.reset_item)
             ),
-            "inspect file diff",
+            "reset the selected action to its default key",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Popup, "reset_item"))
     }
 
-    pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn login_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Quit [{}]",
-                key_config.get_hint(key_config.exit),
+                "Login [{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.login)
             ),
-            "quit gitui application",
+            "open the login dialog",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Tab, "login"))
     }
 
-    pub fn confirm_action(
+    pub fn search_input_enable(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Confirm [{}]",
-                key_config.get_hint(key_config.enter),
+                "Search [{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.search)
             ),
-            "confirm action",
+            "Enable Search input",
             CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Tab, "search"))
     }
 
-    pub fn stashing_save(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Save [{}]",
-                key_config.get_hint(key_config.stashing_save),
-            ),
-            "opens stash name input popup",
-            CMD_GROUP_STASHING,
-        )
-    }
-
-    pub fn stashing_toggle_indexed(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Toggle Staged [{}]",
-                key_config.get_hint(key_config.stashing_toggle_index),
-            ),
-            "toggle including staged files into stash",
-            CMD_GROUP_STASHING,
-        )
-    }
-
-    pub fn stashing_toggle_untracked(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Toggle Untracked [{}]",
-                key_config
-                    .get_hint(key_config.stashing_toggle_untracked),
-            ),
-            "toggle including untracked files into stash",
-            CMD_GROUP_STASHING,
-        )
-    }
-
-    pub fn stashing_confirm_msg(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Stash [{}]",
-                key_config.get_hint(key_config.enter),
-            ),
-            "save files to stash",
-            CMD_GROUP_STASHING,
-        )
-    }
-
-    pub fn stashlist_apply(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn input_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Apply [{}]",
-                key_config.get_hint(key_config.stash_apply),
+                "Input [{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.open_input)
             ),
-            "apply selected stash",
-            CMD_GROUP_STASHES,
-        )
-    }
-
-    pub fn stashlist_drop(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
-        CommandText::new(
-            format!(
-                "Drop [{}]",
-                key_config.get_hint(key_config.stash_drop),
-            ),
-            "drop selected stash",
-            CMD_GROUP_STASHES,
+            "open the input popup",
+            CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Tab, "open_input"))
     }
 
-    pub fn stashlist_pop(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn focus_switch(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Pop [{}]",
-                key_config.get_hint(key_config.enter),
+                "Focus [{}{}{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.focus_left),
+                key_config.borrow().get_hint(key_config.borrow().tab.focus_right),
+                key_config.borrow().get_hint(key_config.borrow().tab.focus_above),
+                key_config.borrow().get_hint(key_config.borrow().tab.focus_below)
             ),
-            "pop selected stash",
-            CMD_GROUP_STASHES,
+            "move focus to a neighbouring pane",
+            CMD_GROUP_LIST,
         )
     }
 
-    pub fn stashlist_inspect(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn focus_cycle(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Inspect [{}]",
-                key_config.get_hint(key_config.focus_right),
+                "Cycle Focus [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.focus_next),
+                key_config.borrow().get_hint(key_config.borrow().tab.focus_prev)
             ),
-            "open stash commit details (allows to diff files)",
-            CMD_GROUP_STASHES,
+            "move focus to the next or previous pane",
+            CMD_GROUP_LIST,
         )
     }
 
-    pub fn log_details_toggle(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn pane_resize(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Details [{}]",
-                key_config.get_hint(key_config.enter),
+                "Resize [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.grow_pane),
+                key_config.borrow().get_hint(key_config.borrow().tab.shrink_pane)
             ),
-            "open details of selected commit",
-            CMD_GROUP_LOG,
+            "grow or shrink the focused pane",
+            CMD_GROUP_LIST,
         )
     }
 
-    pub fn log_details_open(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn toggle_workarea(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Inspect [{}]",
-                key_config.get_hint(key_config.focus_right),
+                "Maximize [{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.toggle_workarea)
             ),
-            "inspect selected commit in detail",
-            CMD_GROUP_LOG,
+            "maximize the focused pane or restore the layout",
+            CMD_GROUP_LIST,
         )
+        .action(ActionId::new(KeyContext::Tab, "toggle_workarea"))
     }
 
-    pub fn blame_file(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn list_navigate(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Blame [{}]",
-                key_config.get_hint(key_config.blame),
+                "Nav [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().list.move_up),
+                key_config.borrow().get_hint(key_config.borrow().list.move_down)
             ),
-            "open blame view of selected file",
-            CMD_GROUP_LOG,
+            "select previous or next line",
+            CMD_GROUP_LIST,
         )
     }
 
-    pub fn log_tag_commit(
+    pub fn list_remove_line(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Tag [{}]",
-                key_config.get_hint(key_config.log_tag_commit),
+                "Remove [{}]",
+                key_config.borrow().get_hint(key_config.borrow().list.remove_line)
             ),
-            "tag commit",
-            CMD_GROUP_LOG,
+            "remove selected line",
+            CMD_GROUP_LIST,
         )
+        .action(ActionId::new(KeyContext::List, "remove_line"))
     }
 
-    pub fn inspect_file_tree(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn list_remove_all(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Files [{}]",
-                key_config.get_hint(key_config.open_file_tree),
+                "Remove All [{}]",
+                key_config.borrow().get_hint(key_config.borrow().list.remove_all)
             ),
-            "inspect file tree at specific revision",
-            CMD_GROUP_LOG,
+            "remove every line",
+            CMD_GROUP_LIST,
         )
+        .action(ActionId::new(KeyContext::List, "remove_all"))
     }
 
-    pub fn tag_commit_confirm_msg(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn input_confirm(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Tag [{}]",
-                key_config.get_hint(key_config.enter),
+                "Confirm [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.confirm)
             ),
-            "tag commit",
-            CMD_GROUP_LOG,
+            "submit the input",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextInput, "confirm"))
     }
 
-    pub fn create_branch_confirm_msg(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn input_cancel(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Create Branch [{}]",
-                key_config.get_hint(key_config.enter),
+                "Cancel [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.cancel)
             ),
-            "create branch",
-            CMD_GROUP_BRANCHES,
+            "discard the input",
+            CMD_GROUP_INPUT,
         )
-            .hide_help()
+        .action(ActionId::new(KeyContext::TextInput, "cancel"))
     }
 
-    pub fn open_branch_create_popup(
+    pub fn input_reveal_password(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Create [{}]",
-                key_config.get_hint(key_config.create_branch),
+                "Reveal [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.reveal_password)
             ),
-            "open create branch popup",
-            CMD_GROUP_BRANCHES,
+            "show the password for a moment",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextInput, "reveal_password"))
     }
 
-    pub fn rename_branch_confirm_msg(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn input_open_editor(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Rename Branch [{}]",
-                key_config.get_hint(key_config.enter),
+                "Editor [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.open_editor)
             ),
-            "rename branch",
-            CMD_GROUP_BRANCHES,
+            "edit the input in $GIT_EDITOR, $VISUAL or $EDITOR",
+            CMD_GROUP_INPUT,
         )
-            .hide_help()
+        .action(ActionId::new(KeyContext::TextInput, "open_editor"))
     }
 
-    pub fn rename_branch_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn form_select(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Rename Branch [{}]",
-                key_config.get_hint(key_config.rename_branch),
+                "Choose [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.cursor_left),
+                key_config.borrow().get_hint(key_config.borrow().text_input.cursor_right)
             ),
-            "rename branch",
-            CMD_GROUP_BRANCHES,
+            "switch to the previous or next option",
+            CMD_GROUP_INPUT,
         )
     }
 
-    pub fn delete_branch_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn message_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Delete [{}]",
-                key_config.get_hint(key_config.delete_branch),
+                "Message [{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.open_message)
             ),
-            "delete a branch",
-            CMD_GROUP_BRANCHES,
+            "open the message editor",
+            CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Tab, "open_message"))
     }
 
-    pub fn merge_branch_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn entry_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Merge [{}]",
-                key_config.get_hint(key_config.merge_branch),
+                "Entry [{}]",
+                key_config.borrow().get_hint(key_config.borrow().tab.open_form)
             ),
-            "merge a branch",
-            CMD_GROUP_BRANCHES,
+            "open the entry form",
+            CMD_GROUP_GENERAL,
         )
+        .action(ActionId::new(KeyContext::Tab, "open_form"))
     }
 
-    pub fn select_branch_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn text_area_submit(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Checkout [{}]",
-                key_config.get_hint(key_config.enter),
+                "Submit [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_area.submit)
             ),
-            "checkout branch",
-            CMD_GROUP_BRANCHES,
+            "submit the text",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextArea, "submit"))
     }
 
-    pub fn toggle_branch_popup(
-        key_config: &SharedKeyConfig,
-        local: bool,
-    ) -> CommandText {
+    pub fn text_area_undo(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "{} [{}]",
-                if local { "Remote" } else { "Local" },
-                key_config.get_hint(key_config.tab_toggle),
+                "Undo/Redo [{}/{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_area.undo),
+                key_config.borrow().get_hint(key_config.borrow().text_area.redo)
             ),
-            "toggle branch type (remote/local)",
-            CMD_GROUP_BRANCHES,
+            "undo or redo the last change",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextArea, "undo"))
     }
 
-    pub fn open_branch_select_popup(
+    pub fn text_area_select_all(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Branches [{}]",
-                key_config.get_hint(key_config.select_branch),
+                "Select All [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_area.select_all)
             ),
-            "open branch popup",
-            CMD_GROUP_BRANCHES,
+            "select the whole text, typing replaces it",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextArea, "select_all"))
     }
 
-    pub fn open_tags_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn widget_toggle(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Tags [{}]",
-                key_config.get_hint(key_config.tags),
+                "Toggle [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.toggle)
             ),
-            "open tags popup",
-            CMD_GROUP_GENERAL,
+            "check or uncheck the option",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextInput, "toggle"))
     }
 
-    pub fn delete_tag_popup(
-        key_config: &SharedKeyConfig,
-    ) -> CommandText {
+    pub fn dropdown_open(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Delete [{}]",
-                key_config.get_hint(key_config.delete_tag),
+                "Open [{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.toggle)
             ),
-            "delete a tag",
-            CMD_GROUP_GENERAL,
+            "list the options, typing filters them",
+            CMD_GROUP_INPUT,
         )
+        .action(ActionId::new(KeyContext::TextInput, "toggle"))
     }
 
-    pub fn select_tag(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn dropdown_pick(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Select commit [{}]",
-                key_config.get_hint(key_config.select_tag),
+                "Pick [{}{}/{}]",
+                key_config.borrow().get_hint(key_config.borrow().popup.scroll_up),
+                key_config.borrow().get_hint(key_config.borrow().popup.scroll_down),
+                key_config.borrow().get_hint(key_config.borrow().text_input.confirm)
             ),
-            "Select commit in revlog",
-            CMD_GROUP_LOG,
+            "move through the matching options and pick one",
+            CMD_GROUP_INPUT,
         )
     }
 
-    pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn spin_step(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
             format!(
-                "Push [{}]",
-                key_config.get_hint(key_config.push),
+                "Step [{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.cursor_left),
+                key_config.borrow().get_hint(key_config.borrow().text_input.cursor_right)
             ),
-            "push to origin",
-            CMD_GROUP_GENERAL,
+            "decrease or increase the number by one step",
+            CMD_GROUP_INPUT,
         )
     }
 
-    pub fn status_force_push(
+    pub fn input_switch_field(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
             format!(
-                "Force Push [{}]",
-                key_config.get_hint(key_config.force_push),
+                "Field [{}{}/{}{}]",
+                key_config.borrow().get_hint(key_config.borrow().text_input.prev_field),
+                key_config.borrow().get_hint(key_config.borrow().text_input.next_field),
+                key_config.borrow().get_hint(key_config.borrow().text_input.focus_prev),
+                key_config.borrow().get_hint(key_config.borrow().text_input.focus_next)
             ),
-            "force push to origin",
-            CMD_GROUP_GENERAL,
+            "focus previous or next input field",
+            CMD_GROUP_INPUT,
         )
     }
+}
 
-    pub fn status_pull(key_config: &SharedKeyConfig) -> CommandText {
-        CommandText::new(
-            format!(
-                "Pull [{}]",
-                key_config.get_hint(key_config.pull),
-            ),
-            "fetch/merge",
-            CMD_GROUP_GENERAL,
-        )
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::keys::KeyConfig;
+
+    use super::commands;
+
+    #[test]
+    fn test_command_actions_resolve() {
+        let key_config = Rc::new(RefCell::new(KeyConfig::default()));
+        let texts = vec![
+            commands::toggle_tabs(&key_config),
+            commands::toggle_tabs_reverse(&key_config),
+            commands::tab_hide(&key_config),
+            commands::tab_show_all(&key_config),
+            commands::help_open(&key_config),
+            commands::quit(&key_config),
+            commands::close_popup(&key_config),
+            commands::keys_editor_open(&key_config),
+            commands::keys_editor_rebind(&key_config),
+            commands::keys_editor_reset(&key_config),
+            commands::login_open(&key_config),
+            commands::search_input_enable(&key_config),
+            commands::input_open(&key_config),
+            commands::toggle_workarea(&key_config),
+            commands::list_remove_line(&key_config),
+            commands::list_remove_all(&key_config),
+            commands::notifications_open(&key_config),
+            commands::logout(&key_config),
+            commands::confirm_choose(&key_config),
+            commands::input_confirm(&key_config),
+            commands::input_cancel(&key_config),
+            commands::input_reveal_password(&key_config),
+            commands::input_open_editor(&key_config),
+            commands::widget_toggle(&key_config),
+            commands::dropdown_open(&key_config),
+            commands::message_open(&key_config),
+            commands::entry_open(&key_config),
+            commands::text_area_submit(&key_config),
+            commands::text_area_undo(&key_config),
+            commands::text_area_select_all(&key_config),
+        ];
+
+        for text in texts {
+            let id = text.action.unwrap();
+            assert!(
+                key_config.borrow().bindings().iter().any(|b| b.id() == id),
+                "{:?}",
+                id
+            );
+        }
     }
 }
@@ -0,0 +1,158 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Result};
+use ron::ser::{PrettyConfig, to_string_pretty};
+use serde::{Deserialize, Serialize};
+
+use crate::{args::get_app_config_path, ui::layout::LayoutNode};
+
+use super::Tab;
+
+/// order and visibility of the tabs, identified by `Tab::id`
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct TabsConfig {
+    pub order: Vec<String>,
+    pub hidden: Vec<String>,
+    /// tabs that need a login on top of those declaring it themselves
+    pub require_login: Vec<String>,
+}
+
+impl TabsConfig {
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("tabs.ron"))
+    }
+
+    pub fn save(&self, file: PathBuf) -> Result<()> {
+        let mut file = File::create(file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    /// a missing file means all tabs in registration order
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// positions of `ids` sorted by the configured order,
+    /// tabs the config does not know keep their relative order at the end
+    pub fn arrange(&self, ids: &[&str]) -> Vec<usize> {
+        let mut res: Vec<usize> = (0..ids.len()).collect();
+        res.sort_by_key(|i| {
+            self.order
+                .iter()
+                .position(|o| o == ids[*i])
+                .unwrap_or(usize::MAX)
+        });
+        res
+    }
+
+    pub fn is_hidden(&self, id: &str) -> bool {
+        self.hidden.iter().any(|h| h == id)
+    }
+
+    pub fn requires_login(&self, id: &str) -> bool {
+        self.require_login.iter().any(|r| r == id)
+    }
+}
+
+/// layouts of the tabs keyed by `Tab::id`, read from `layout.ron`,
+/// tabs without an entry keep their built in layout
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub tabs: BTreeMap<String, LayoutNode>,
+}
+
+impl LayoutConfig {
+    pub fn get_config_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("layout.ron"))
+    }
+
+    fn read_file(config_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(config_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(ron::de::from_bytes(&buffer)?)
+    }
+
+    /// a missing file is not an error, nothing gets overridden then
+    pub fn init(file: PathBuf) -> Result<Self> {
+        if file.exists() {
+            Self::read_file(file.clone()).map_err(|e| {
+                anyhow!("{}: {}", file.to_string_lossy(), e)
+            })
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// checks every layout against the panes its tab offers
+    pub fn validate(&self, tabs: &[Box<dyn Tab>]) -> Result<()> {
+        for (id, layout) in &self.tabs {
+            let tab = tabs
+                .iter()
+                .find(|t| t.id() == id)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "layout for unknown tab '{}', expected one of: {}",
+                        id,
+                        tabs.iter().map(|t| t.id()).collect::<Vec<_>>().join(", ")
+                    )
+                })?;
+            if tab.panes().is_empty() {
+                return Err(anyhow!("tab '{}' has no configurable layout", id));
+            }
+            layout
+                .validate(tab.panes())
+                .map_err(|e| anyhow!("layout of tab '{}': {}", id, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LayoutConfig, TabsConfig};
+    use crate::tabs::T02;
+
+    #[test]
+    fn test_arrange() {
+        let config = TabsConfig {
+            order: vec!["t03".to_string(), "gone".to_string(), "t01".to_string()],
+            ..TabsConfig::default()
+        };
+
+        assert_eq!(config.arrange(&["t01", "t02", "t03"]), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_layout_example() {
+        let config =
+            LayoutConfig::read_file("layout_example.ron".into()).unwrap();
+        let layout = &config.tabs["t02"];
+
+        assert!(layout.validate(&T02::PANES).is_ok());
+        assert!(layout.validate(&["search", "list1"]).is_err());
+    }
+}
@@ -1,8 +1,111 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use tui::{layout::Rect, Frame};
+
+use crate::{
+    components::{Component, Popup},
+    keys::SharedKeyConfig,
+    queue::{Action, Queue},
+    settings::SharedSettings,
+    ui::{layout::LayoutNode, style::SharedTheme, UiBackend},
+};
+
+pub use config::{LayoutConfig, TabsConfig};
 pub use t01::T01;
 pub use t02::T02;
 pub use t03::T03;
 
+mod config;
 mod t01;
 mod t02;
 mod t03;
 
+/// a top level page that gets registered with the `App`
+pub trait Tab: Component {
+    /// stable name used in the tabs config file
+    fn id(&self) -> &'static str;
+
+    /// name shown in the tab bar
+    fn title(&self) -> String;
+
+    /// drawn in front of the title
+    fn icon(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// key that selects the tab, `None` uses the next number key
+    fn hotkey(&self) -> Option<KeyEvent> {
+        None
+    }
+
+    /// panes a layout of this tab may place, empty if it has no layout
+    fn panes(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// replaces the built in layout, `layout` is validated against `panes`
+    fn set_layout(&mut self, _layout: LayoutNode) {}
+
+    /// popups owned by the tab, named for the popup stack,
+    /// the app draws them and routes their input through the tab
+    fn popups(&self) -> Vec<(&'static str, &dyn Popup)> {
+        Vec::new()
+    }
+
+    fn popups_mut(&mut self) -> Vec<(&'static str, &mut dyn Popup)> {
+        Vec::new()
+    }
+
+    /// the pane holding the focus, a popup gives it back when it closes
+    fn focused_pane(&self) -> Option<String> {
+        None
+    }
+
+    fn restore_focus(&mut self, _pane: &str) {}
+
+    /// only shown while somebody is logged in
+    fn requires_auth(&self) -> bool {
+        false
+    }
+
+    /// shows the login dialog, `false` if the tab has none
+    fn open_login(&mut self) -> bool {
+        false
+    }
+
+    /// runs an action the user confirmed, `false` if it is not the tab's
+    fn action_confirmed(&mut self, _action: &Action) -> bool {
+        false
+    }
+
+    /// called on every tick of the app, picks up background work
+    fn on_tick(&mut self) {}
+
+    /// object safe version of `DrawableComponent::draw`,
+    /// popups are not part of it
+    fn draw_tab(&self, f: &mut Frame<UiBackend>, rect: Rect) -> Result<()>;
+}
+
+/// every tab of the app in registration order
+pub fn default_tabs(
+    queue: &Queue,
+    theme: &SharedTheme,
+    key_config: &SharedKeyConfig,
+    settings: &SharedSettings,
+) -> Vec<Box<dyn Tab>> {
+    vec![
+        Box::new(T01::new(queue, theme.clone(), key_config.clone())),
+        Box::new(T02::new(
+            queue,
+            theme.clone(),
+            key_config.clone(),
+            settings.clone(),
+        )),
+        Box::new(T03::new(
+            queue,
+            theme.clone(),
+            key_config.clone(),
+            settings.clone(),
+        )),
+    ]
+}
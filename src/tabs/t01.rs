@@ -1,60 +1,68 @@
-use std::cell::RefCell;
-
 use anyhow::Result;
 use crossterm::event::Event;
-use log::info;
 use tui::backend::Backend;
 use tui::Frame;
-use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::layout::Rect;
 use tui::widgets::{Block, Borders};
 
 use crate::{
+    auth::AuthConfig,
+    tabs::Tab,
+    ui::UiBackend,
     accessors,
     components::{
         CommandBlocking, CommandInfo,
         // CommitList,
-        Component, DrawableComponent, event_pump, EventState, visibility_blocking,
+        action_pump, command_pump, Component, DrawableComponent, edited_pump,
+        event_pump,
+        EventState,
+        visibility_blocking,
     },
-    keys::SharedKeyConfig,
-    setup_popups,
-    // queue::{Action, InternalEvent, Queue},
+    keys::{ActionId, SharedKeyConfig},
+    queue::{InternalEvent, Queue},
     strings,
     ui::style::SharedTheme,
 };
-use crate::cmdbar::CommandBar;
-use crate::components::LoginComponent;
+use crate::components::{LoginComponent, MsgKind, Popup};
 
 pub struct T01 {
     visible: bool,
     theme: SharedTheme,
     login: LoginComponent,
-    cmdbar: RefCell<CommandBar>,
-    // queue: Queue,
+    queue: Queue,
     key_config: SharedKeyConfig,
 }
 
 impl T01 {
     accessors!(self, [login]);
 
-    setup_popups!(
-        self,
-        [
-            login
-        ]
-    );
     ///
     pub fn new(
-        // queue: &Queue,
+        queue: &Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
+        let authenticator = AuthConfig::get_config_file()
+            .and_then(AuthConfig::init)
+            .and_then(|config| config.authenticator())
+            .map_err(|e| {
+                log::warn!("auth config: {}", e);
+                queue.push(InternalEvent::ShowMsg(
+                    MsgKind::Warning,
+                    format!("auth config: {}", e),
+                ));
+            })
+            .ok();
+
         Self {
             visible: false,
-            login: LoginComponent::new(key_config.clone()),
-            cmdbar: RefCell::new(CommandBar::new(
+            login: LoginComponent::new(
+                authenticator,
+                queue,
                 theme.clone(),
                 key_config.clone(),
-            )),
+            ),
+            queue: queue.clone(),
             theme,
             key_config,
         }
@@ -84,12 +92,43 @@ impl DrawableComponent for T01 {
     ) -> Result<()> {
         let inner = Block::default().title("t01").borders(Borders::ALL);
         f.render_widget(inner, rect);
-        // self.login.draw(f, rect);
-        self.draw_popups(f)?;
         Ok(())
     }
 }
 
+impl Tab for T01 {
+    fn id(&self) -> &'static str {
+        "t01"
+    }
+
+    fn title(&self) -> String {
+        strings::tab_t01(&self.key_config)
+    }
+
+    fn popups(&self) -> Vec<(&'static str, &dyn Popup)> {
+        vec![("login", &self.login)]
+    }
+
+    fn popups_mut(&mut self) -> Vec<(&'static str, &mut dyn Popup)> {
+        vec![("login", &mut self.login)]
+    }
+
+    fn open_login(&mut self) -> bool {
+        self.login.show().is_ok()
+    }
+
+    fn on_tick(&mut self) {
+        self.login.on_tick();
+        if let Some(identity) = self.login.take_identity() {
+            self.queue.push(InternalEvent::LoggedIn(identity));
+        }
+    }
+
+    fn draw_tab(&self, f: &mut Frame<UiBackend>, rect: Rect) -> Result<()> {
+        self.draw(f, rect)
+    }
+}
+
 impl Component for T01 {
     fn commands(
         &self,
@@ -97,35 +136,12 @@ impl Component for T01 {
         force_all: bool,
     ) -> CommandBlocking {
         if self.visible || force_all {
-            // self.list.commands(out, force_all);
+            command_pump(out, force_all, self.components().as_slice());
 
-            // let selection_valid =
-            //     self.list.selected_entry().is_some();
             out.push(CommandInfo::new(
-                strings::commands::stashlist_pop(&self.key_config),
-                // selection_valid,
-                true,
-                true,
-            ));
-            out.push(CommandInfo::new(
-                strings::commands::stashlist_apply(&self.key_config),
-                // selection_valid,
-                true,
-                true,
-            ));
-            out.push(CommandInfo::new(
-                strings::commands::stashlist_drop(&self.key_config),
-                // selection_valid,
-                true,
-                true,
-            ));
-            out.push(CommandInfo::new(
-                strings::commands::stashlist_inspect(
-                    &self.key_config,
-                ),
-                // selection_valid,
-                true,
+                strings::commands::login_open(&self.key_config),
                 true,
+                !self.login.is_visible(),
             ));
         }
 
@@ -138,25 +154,20 @@ impl Component for T01 {
     ) -> Result<EventState> {
         if self.is_visible() {
             // if let Event::Key(k) = ev {
-            //     if k == self.key_config.login {
+            //     if k == self.key_config.borrow().login {
             //         self.login.show();
             //         return Ok(EventState::Consumed);
-            //     } else if k == self.key_config.stash_apply {
+            //     } else if k == self.key_config.borrow().stash_apply {
             //         self.apply_stash();
-            //     } else if k == self.key_config.stash_drop {
+            //     } else if k == self.key_config.borrow().stash_drop {
             //         self.drop_stash();
-            //     } else if k == self.key_config.stash_open {
+            //     } else if k == self.key_config.borrow().stash_open {
             //         self.inspect();
             //     }
             // }
             if event_pump(ev, self.components_mut().as_mut_slice())?
                 .is_consumed()
             {
-                let msg = self.login.get_msg();
-                if !msg.is_empty() {
-                    // self.list.list_item_add(msg);
-                    info!("msg is {:?}",msg);
-                }
                 return Ok(EventState::Consumed);
             }
         }
@@ -164,6 +175,18 @@ impl Component for T01 {
         Ok(EventState::NotConsumed)
     }
 
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if self.is_visible() {
+            return action_pump(action, self.components_mut().as_mut_slice());
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        edited_pump(text, self.components_mut().as_mut_slice())
+    }
+
     fn is_visible(&self) -> bool {
         self.visible
     }
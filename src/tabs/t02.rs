@@ -1,33 +1,40 @@
 use std::cell::Cell;
 
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::{
+    Event, KeyCode, MouseButton, MouseEvent, MouseEventKind,
+};
 use log::info;
-use serde::__private::ser::constrain;
-use tui::layout::{Constraint, Direction, Layout};
+use tui::layout::Rect;
 use tui::widgets::{Block, Borders};
+use tui::Frame;
 
 pub use crate::{
-    accessors,
     components::{
         CommandBlocking,
         CommandInfo,
         Component,
         // CommitList,
-        DrawableComponent, event_pump, EventState, InputMode, SearchComponent, visibility_blocking,
+        DrawableComponent, action_pump, edited_pump, event_pump, EventState, InputMode, SearchComponent, visibility_blocking,
     },
-    keys::SharedKeyConfig,
-    // queue::{Action, InternalEvent, Queue},
+    keys::{ActionId, KeyContext, SharedKeyConfig},
+    queue::{Action, Queue},
 
     ui::style::SharedTheme,
 };
 use crate::components::ListComponent;
+use crate::settings::SharedSettings;
 use crate::strings;
+use crate::tabs::Tab;
+use crate::ui::layout::{
+    FocusDirection, LayoutNode, PaneLayout, SplitDirection, SplitSize,
+};
+use crate::ui::UiBackend;
 
-enum Focus {
-    List1,
-    List2,
-}
+const PANE_SEARCH: &str = "search";
+const PANE_LIST1: &str = "list1";
+const PANE_LIST2: &str = "list2";
+const FOCUSABLE: [&str; 3] = [PANE_LIST1, PANE_LIST2, PANE_SEARCH];
 
 pub struct T02 {
     // list: CommitList,
@@ -37,36 +44,71 @@ pub struct T02 {
     search: SearchComponent,
     list: ListComponent,
     list2: ListComponent,
+    layout: PaneLayout,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
 }
 
 impl T02 {
-    accessors!(self, [list,list2,search]);
     ///
     pub fn new(
-        // queue: &Queue,
+        queue: &Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        settings: SharedSettings,
     ) -> Self {
         let mut t02 =
             Self {
                 visible: false,
                 msg: vec![],
-                search: SearchComponent::new(key_config.clone()),
+                search: SearchComponent::new(queue, key_config.clone()),
                 list: ListComponent::new_with_title("list1".to_string(),
+                                                    queue,
                                                     theme.clone(),
-                                                    key_config.clone()),
+                                                    key_config.clone(),
+                                                    settings.clone()),
                 list2: ListComponent::new_with_title("list2".to_string(),
+                                                     queue,
                                                      theme.clone(),
-                                                     key_config.clone()),
+                                                     key_config.clone(),
+                                                     settings),
+                layout: PaneLayout::new(Self::default_layout(), &FOCUSABLE),
                 theme,
                 key_config,
             };
-        t02.list.focus(true);
+        t02.sync_focus();
         return t02;
     }
 
+    /// everything a layout may place
+    pub const PANES: [&'static str; 3] = [PANE_SEARCH, PANE_LIST1, PANE_LIST2];
+
+    /// search on top, both lists side by side below it
+    pub fn default_layout() -> LayoutNode {
+        LayoutNode::Split {
+            direction: SplitDirection::Vertical,
+            children: vec![
+                (SplitSize::Length(3), LayoutNode::Pane(PANE_SEARCH.to_string())),
+                (
+                    SplitSize::Min(1),
+                    LayoutNode::Split {
+                        direction: SplitDirection::Horizontal,
+                        children: vec![
+                            (
+                                SplitSize::Percentage(30),
+                                LayoutNode::Pane(PANE_LIST1.to_string()),
+                            ),
+                            (
+                                SplitSize::Percentage(70),
+                                LayoutNode::Pane(PANE_LIST2.to_string()),
+                            ),
+                        ],
+                    },
+                ),
+            ],
+        }
+    }
+
 
     ///
     pub fn update(&mut self) -> Result<()> {
@@ -83,17 +125,89 @@ impl T02 {
         Ok(())
     }
 
-    fn toggle_focus(&mut self) {
-        if self.list.focused() {
-            self.list.focus(false);
-            self.list2.focus(true);
-            return;
+    /// the components whose pane is part of the layout,
+    /// the others get neither keys nor focus
+    fn placed_mut(&mut self) -> Vec<&mut dyn Component> {
+        let layout = &self.layout;
+        let mut placed: Vec<&mut dyn Component> = Vec::new();
+        if layout.contains(PANE_LIST1) {
+            placed.push(&mut self.list);
+        }
+        if layout.contains(PANE_LIST2) {
+            placed.push(&mut self.list2);
+        }
+        if layout.contains(PANE_SEARCH) {
+            placed.push(&mut self.search);
         }
-        if self.list2.focused() {
-            self.list2.focus(false);
-            self.list.focus(true);
+        placed
+    }
+
+    /// lets the list in the focused pane take the keys,
+    /// while searching the last focused list keeps receiving results
+    fn sync_focus(&mut self) {
+        match self.layout.focused() {
+            PANE_LIST1 => {
+                self.list.focus(true);
+                self.list2.focus(false);
+            }
+            PANE_LIST2 => {
+                self.list.focus(false);
+                self.list2.focus(true);
+            }
+            _ => (),
+        }
+        if self.search.focused() {
+            self.list.unselected();
+            self.list.disable_event();
+            self.list2.unselected();
+            self.list2.disable_event();
+        }
+    }
+
+    /// moves focus to `pane`, focusing the search starts editing
+    /// and leaving it hands focus back to where it came from
+    fn focus_pane(&mut self, pane: &str) {
+        if !self.layout.contains(pane) {
             return;
         }
+        if pane == PANE_SEARCH {
+            if self.layout.push_focus(PANE_SEARCH) {
+                self.search.set_editing(true);
+            }
+        } else {
+            if self.search.focused() {
+                self.search.set_editing(false);
+                self.layout.focus_back();
+            }
+            self.layout.focus(pane);
+        }
+        self.sync_focus();
+    }
+
+    /// adds what got searched for to the focused list
+    fn after_input(&mut self) {
+        self.sync_search_focus();
+        let msg = self.search.get_msg();
+        if !msg.is_empty() {
+            if self.list.focused() {
+                self.list.list_item_add(msg);
+            } else {
+                self.list2.list_item_add(msg);
+            }
+        }
+    }
+
+    /// keeps the focused pane in line with the search editing state
+    /// after the search handled a key itself
+    fn sync_search_focus(&mut self) {
+        let editing = self.search.focused();
+        let on_search = self.layout.focused() == PANE_SEARCH;
+        if editing && !on_search {
+            self.layout.push_focus(PANE_SEARCH);
+        } else if !editing && on_search {
+            self.layout.focus_back();
+        }
+        self.sync_focus();
     }
 }
 
@@ -104,32 +218,57 @@ impl DrawableComponent for T02 {
         rect: tui::layout::Rect,
     ) -> Result<()> {
         if self.is_visible() {
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(
-                    [
-                        Constraint::Length(3),
-                        Constraint::Length(9)
-                    ]
-                ).split(rect);
-            let list_chunk = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints(
-                    [
-                        Constraint::Percentage(30),
-                        Constraint::Percentage(70)
-                    ]
-                ).split(chunks[1]);
-
-            self.search.draw(f, chunks[0])?;
-            self.list.draw(f, list_chunk[0])?;
-            self.list2.draw(f, list_chunk[1])?;
+            for (pane, area) in self.layout.areas(rect) {
+                match pane.as_str() {
+                    PANE_SEARCH => self.search.draw(f, area)?,
+                    PANE_LIST1 => self.list.draw(f, area)?,
+                    PANE_LIST2 => self.list2.draw(f, area)?,
+                    _ => (),
+                }
+            }
         }
         Ok(())
     }
 }
 
 
+impl Tab for T02 {
+    fn id(&self) -> &'static str {
+        "t02"
+    }
+
+    fn title(&self) -> String {
+        strings::tab_t02(&self.key_config)
+    }
+
+    fn panes(&self) -> &'static [&'static str] {
+        &Self::PANES
+    }
+
+    fn focused_pane(&self) -> Option<String> {
+        Some(self.layout.focused().to_string())
+    }
+
+    fn restore_focus(&mut self, pane: &str) {
+        if self.layout.focused() != pane {
+            self.focus_pane(pane);
+        }
+    }
+
+    fn action_confirmed(&mut self, action: &Action) -> bool {
+        self.list.action_confirmed(action) || self.list2.action_confirmed(action)
+    }
+
+    fn set_layout(&mut self, layout: LayoutNode) {
+        self.layout = PaneLayout::new(layout, &FOCUSABLE);
+        self.sync_focus();
+    }
+
+    fn draw_tab(&self, f: &mut Frame<UiBackend>, rect: Rect) -> Result<()> {
+        self.draw(f, rect)
+    }
+}
+
 impl Component for T02 {
     fn commands(
         &self,
@@ -137,37 +276,45 @@ impl Component for T02 {
         force_all: bool,
     ) -> CommandBlocking {
         if self.visible || force_all {
-            // self.list.commands(out, force_all);
-            self.search.commands(out, force_all);
-
-            // let selection_valid =
-            //     self.list.selected_entry().is_some();
-            // out.push(CommandInfo::new(
-            //     strings::commands::stashing_save(&self.key_config),
-            //     // selection_valid,
-            //     true,
-            //     true,
-            // ));
-            out.push(CommandInfo::new(
-                strings::commands::log_details_open(&self.key_config),
-                // selection_valid,
-                true,
-                true,
-            ));
+            if self.layout.contains(PANE_SEARCH) {
+                self.search.commands(out, force_all);
+            }
+
+            let normal =
+                matches!(self.search.get_input_mode(), InputMode::Normal);
             out.push(CommandInfo::new(
-                strings::commands::stashlist_drop(&self.key_config),
-                // selection_valid,
-                true,
+                strings::commands::focus_switch(&self.key_config),
                 true,
+                normal,
             ));
+            out.push(
+                CommandInfo::new(
+                    strings::commands::focus_cycle(&self.key_config),
+                    true,
+                    normal,
+                )
+                .hidden(),
+            );
+            out.push(
+                CommandInfo::new(
+                    strings::commands::pane_resize(&self.key_config),
+                    !self.layout.is_maximized(),
+                    normal,
+                )
+                .hidden(),
+            );
             out.push(CommandInfo::new(
-                strings::commands::stashlist_inspect(
-                    &self.key_config,
-                ),
-                // selection_valid,
-                true,
+                strings::commands::toggle_workarea(&self.key_config),
                 true,
+                normal,
             ));
+            if normal {
+                if self.list.focused() {
+                    self.list.commands(out, force_all);
+                } else {
+                    self.list2.commands(out, force_all);
+                }
+            }
         }
 
         visibility_blocking(self)
@@ -179,11 +326,11 @@ impl Component for T02 {
     ) -> Result<EventState> {
         if self.is_visible() {
             // if let Event::Key(k) = ev {
-            //     if k == self.key_config.focus_left {
+            //     if k == self.key_config.borrow().focus_left {
             //         self.toggle_focus();
             //         return Ok(EventState::Consumed);
             //     }
-            //     if k == self.key_config.focus_right {
+            //     if k == self.key_config.borrow().focus_right {
             //         self.toggle_focus();
             //         return Ok(EventState::Consumed);
             //     }
@@ -194,45 +341,94 @@ impl Component for T02 {
                     self.list2.enable_event();
 
                     if let Event::Key(k) = ev {
-                        if k == self.key_config.focus_left {
-                            self.toggle_focus();
+                        let keys = self.key_config.borrow().tab.clone();
+                        let direction = if k == keys.focus_left {
+                            Some(FocusDirection::Left)
+                        } else if k == keys.focus_right {
+                            Some(FocusDirection::Right)
+                        } else if k == keys.focus_above {
+                            Some(FocusDirection::Above)
+                        } else if k == keys.focus_below {
+                            Some(FocusDirection::Below)
+                        } else {
+                            None
+                        };
+                        if let Some(direction) = direction {
+                            if let Some(pane) = self.layout.neighbour(direction)
+                            {
+                                self.focus_pane(&pane);
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+                        if k == keys.focus_next || k == keys.focus_prev {
+                            if let Some(pane) =
+                                self.layout.cycled(k == keys.focus_next)
+                            {
+                                self.focus_pane(&pane);
+                            }
                             return Ok(EventState::Consumed);
                         }
-                        if k == self.key_config.focus_right {
-                            self.toggle_focus();
+                        if k == keys.grow_pane || k == keys.shrink_pane {
+                            self.layout.resize(k == keys.grow_pane);
+                            return Ok(EventState::Consumed);
+                        }
+                        if k == keys.toggle_workarea {
+                            self.layout.toggle_maximized();
                             return Ok(EventState::Consumed);
                         }
                     }
                 }
                 _ => {}
             }
-            if event_pump(ev, self.components_mut().as_mut_slice())?
-                .is_consumed()
+            if let Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) = ev
             {
-                match self.search.get_input_mode() {
-                    InputMode::Editing => {
-                        self.list.unselected();
-                        self.list.disable_event();
-                        self.list2.unselected();
-                        self.list2.disable_event();
-                    }
-                    _ => {}
-                }
-                let msg = self.search.get_msg();
-                if !msg.is_empty() {
-                    if self.list.focused() {
-                        self.list.list_item_add(msg);
-                    } else {
-                        self.list2.list_item_add(msg);
-                    }
+                if let Some(pane) = self.layout.pane_at(column, row) {
+                    self.focus_pane(&pane);
+                    return Ok(EventState::Consumed);
                 }
+            }
+            if event_pump(ev, self.placed_mut().as_mut_slice())?
+                .is_consumed()
+            {
+                self.after_input();
+                return Ok(EventState::Consumed);
+            }
+        }
+
+        Ok(EventState::NotConsumed)
+    }
+
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.is_visible() {
+            return Ok(EventState::NotConsumed);
+        }
+        if let InputMode::Normal = self.search.get_input_mode() {
+            self.list.enable_event();
+            self.list2.enable_event();
+            if action == ActionId::new(KeyContext::Tab, "toggle_workarea") {
+                self.layout.toggle_maximized();
                 return Ok(EventState::Consumed);
             }
         }
+        if action_pump(action, self.placed_mut().as_mut_slice())?
+            .is_consumed()
+        {
+            self.after_input();
+            return Ok(EventState::Consumed);
+        }
 
         Ok(EventState::NotConsumed)
     }
 
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        edited_pump(text, self.placed_mut().as_mut_slice())
+    }
+
     fn is_visible(&self) -> bool {
         self.visible
     }
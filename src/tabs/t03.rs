@@ -1,29 +1,79 @@
 use anyhow::Result;
 use crossterm::event::Event;
-use tui::widgets::{Block, Borders};
+use tui::layout::Rect;
+use tui::widgets::{Block, Borders, Paragraph, Wrap};
+use tui::Frame;
 
 use crate::{accessors, components::{
     CommandBlocking, CommandInfo,
     // CommitList,
     Component, DrawableComponent, EventState, visibility_blocking,
 },
-            keys::SharedKeyConfig,
-            setup_popups,
-            // queue::{Action, InternalEvent, Queue},
+            keys::{ActionId, KeyContext, SharedKeyConfig},
+            queue::Queue,
             strings,
             ui::style::SharedTheme,
 };
-use crate::components::{event_pump, PopInputComponent};
+use crate::components::{
+    action_pump, edited_pump, event_pump, FieldValue, FormComponent,
+    FormField, FormValues, FromForm, PopInputComponent, Popup,
+    TextAreaComponent,
+};
+use crate::settings::SharedSettings;
+use crate::tabs::Tab;
+use crate::ui::UiBackend;
 
 // use asyncgit::{
 //     CWD,
 //     sync::{self, CommitId},
 // };
 
+/// titles longer than this do not pass the entry form
+const ENTRY_TITLE_MAX: usize = 40;
+
+/// what the entry form submits
+struct Entry {
+    title: String,
+    kind: String,
+    priority: String,
+    minutes: Option<i64>,
+    details: String,
+}
+
+impl FromForm for Entry {
+    fn from_form(values: &FormValues) -> Result<Self> {
+        Ok(Self {
+            title: values.text("title")?,
+            kind: values.text("kind")?,
+            priority: values.text("priority")?,
+            minutes: values.number("minutes")?,
+            details: values.text("details")?,
+        })
+    }
+}
+
+impl Entry {
+    /// what the tab shows of the entry
+    fn text(&self) -> String {
+        let minutes = self
+            .minutes
+            .map(|m| format!(", {} min", m))
+            .unwrap_or_default();
+        format!(
+            "{} ({}, {}{})\n{}",
+            self.title, self.kind, self.priority, minutes, self.details
+        )
+    }
+}
+
 pub struct T03 {
     // list: CommitList,
     visible: bool,
     popinput: PopInputComponent,
+    message: TextAreaComponent,
+    form: FormComponent,
+    /// the last submitted message or entry, shown in the tab
+    last_message: String,
     // queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -33,20 +83,17 @@ impl T03 {
     accessors!(
         self,
         [
-         popinput
+         popinput,
+         message,
+         form
         ]
     );
-    // setup_popups!(
-    //     self,
-    //     [
-    //         popinput
-    //     ]
-    // );
     ///
     pub fn new(
-        // queue: &Queue,
+        queue: &Queue,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
+        settings: SharedSettings,
     ) -> Self {
         Self {
             visible: false,
@@ -56,7 +103,17 @@ impl T03 {
             //     key_config.clone(),
             // ),
             // queue: queue.clone(),
-            popinput: PopInputComponent::new(key_config.clone()),
+            popinput: PopInputComponent::new(queue, key_config.clone()),
+            form: Self::entry_form(queue, &theme, &key_config),
+            message: TextAreaComponent::new(
+                &strings::message_title(&key_config),
+                &strings::commit_msg(&key_config),
+                queue,
+                theme.clone(),
+                key_config.clone(),
+                settings,
+            ),
+            last_message: String::new(),
             theme,
             key_config,
         }
@@ -76,6 +133,63 @@ impl T03 {
         Ok(())
     }
 
+    fn entry_form(
+        queue: &Queue,
+        theme: &SharedTheme,
+        key_config: &SharedKeyConfig,
+    ) -> FormComponent {
+        let too_long = strings::entry_too_long(key_config, ENTRY_TITLE_MAX);
+        let options = |options: &[&str]| {
+            options.iter().map(|o| (*o).to_string()).collect::<Vec<_>>()
+        };
+        let mut form = FormComponent::new(
+            &strings::entry_title(key_config),
+            queue,
+            theme.clone(),
+            key_config.clone(),
+        )
+        .width(50)
+        .field(FormField::text("title", "Title").required().validate(
+            move |value| match value {
+                FieldValue::Text(text)
+                    if text.chars().count() > ENTRY_TITLE_MAX =>
+                {
+                    Some(too_long.clone())
+                }
+                _ => None,
+            },
+        ))
+        .field(
+            FormField::select("kind", "Kind", options(&["note", "task", "bug"]))
+                .required(),
+        )
+        .field(FormField::radio(
+            "priority",
+            "Priority",
+            options(&["low", "normal", "high"]),
+        ))
+        .field(FormField::number("minutes", "Minutes", 0, 480).step(15))
+        .field(FormField::multi_line("details", "Details", 3));
+        form.set_choice("priority", "normal");
+        form
+    }
+
+    /// keeps what the message editor or the entry form got submitted with
+    fn take_message(&mut self) {
+        if let Some(message) = self.message.take_submitted() {
+            self.last_message = message;
+        }
+        match self.form.take_submitted::<Entry>() {
+            Some(Ok(entry)) => {
+                self.last_message = entry.text();
+                self.form.hide();
+                self.form.set_choice("priority", "normal");
+            }
+            Some(Err(e)) => log::error!("entry form: {:#}", e),
+            None => (),
+        }
+    }
+
     fn apply_stash(&mut self) {
         // if let Some(e) = self.list.selected_entry() {
         //     match sync::stash_apply(CWD, e.id, false) {
@@ -150,13 +264,46 @@ impl DrawableComponent for T03 {
         rect: tui::layout::Rect,
     ) -> Result<()> {
         let inner = Block::default().title("t03").borders(Borders::ALL);
-        f.render_widget(inner, rect);
-        self.popinput.draw(f, rect);
-        // self.draw_popups(f)?;
+        f.render_widget(
+            Paragraph::new(self.last_message.as_str())
+                .block(inner)
+                .wrap(Wrap { trim: false }),
+            rect,
+        );
         Ok(())
     }
 }
 
+impl Tab for T03 {
+    fn id(&self) -> &'static str {
+        "t03"
+    }
+
+    fn title(&self) -> String {
+        strings::tab_t03(&self.key_config)
+    }
+
+    fn popups(&self) -> Vec<(&'static str, &dyn Popup)> {
+        vec![
+            ("input", &self.popinput),
+            ("message", &self.message),
+            ("form", &self.form),
+        ]
+    }
+
+    fn popups_mut(&mut self) -> Vec<(&'static str, &mut dyn Popup)> {
+        vec![
+            ("input", &mut self.popinput),
+            ("message", &mut self.message),
+            ("form", &mut self.form),
+        ]
+    }
+
+    fn draw_tab(&self, f: &mut Frame<UiBackend>, rect: Rect) -> Result<()> {
+        self.draw(f, rect)
+    }
+}
+
 impl Component for T03 {
     fn commands(
         &self,
@@ -164,15 +311,27 @@ impl Component for T03 {
         force_all: bool,
     ) -> CommandBlocking {
         if self.visible || force_all {
-            // self.list.commands(out, force_all);
+            self.popinput.commands(out, force_all);
+            self.message.commands(out, force_all);
+            self.form.commands(out, force_all);
 
-            // let selection_valid =
-            //     self.list.selected_entry().is_some();
+            let popup_open = self.popinput.is_visible()
+                || self.message.is_visible()
+                || self.form.is_visible();
             out.push(CommandInfo::new(
-                strings::commands::ignore_item(&self.key_config),
-                // selection_valid,
+                strings::commands::input_open(&self.key_config),
                 true,
+                !popup_open,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::message_open(&self.key_config),
+                true,
+                !popup_open,
+            ));
+            out.push(CommandInfo::new(
+                strings::commands::entry_open(&self.key_config),
                 true,
+                !popup_open,
             ));
         }
 
@@ -187,26 +346,54 @@ impl Component for T03 {
             // if self.list.event(ev)?.is_consumed() {
             //     return Ok(EventState::Consumed);
             // }
-            if let Event::Key(k) = ev {
-                if k == self.key_config.status_ignore_file {
-                    self.popinput.show();
-                    return Ok(EventState::Consumed);
-                } else if k == self.key_config.stash_drop {
-                    self.drop_stash();
-                } else if k == self.key_config.stash_open {
-                    self.inspect();
-                }
-            }
             if event_pump(ev, self.components_mut().as_mut_slice())?
                 .is_consumed()
             {
+                self.take_message();
                 return Ok(EventState::Consumed);
             }
+            if let Event::Key(k) = ev {
+                if k == self.key_config.borrow().tab.open_input {
+                    self.popinput.show()?;
+                    return Ok(EventState::Consumed);
+                }
+                if k == self.key_config.borrow().tab.open_message {
+                    self.message.show()?;
+                    return Ok(EventState::Consumed);
+                }
+                if k == self.key_config.borrow().tab.open_form {
+                    self.form.show()?;
+                    return Ok(EventState::Consumed);
+                }
+            }
         }
 
         Ok(EventState::NotConsumed)
     }
 
+    fn run_action(&mut self, action: ActionId) -> Result<EventState> {
+        if !self.is_visible() {
+            return Ok(EventState::NotConsumed);
+        }
+        if action_pump(action, self.components_mut().as_mut_slice())?
+            .is_consumed()
+        {
+            self.take_message();
+            return Ok(EventState::Consumed);
+        }
+        match (action.context, action.action) {
+            (KeyContext::Tab, "open_input") => self.popinput.show()?,
+            (KeyContext::Tab, "open_message") => self.message.show()?,
+            (KeyContext::Tab, "open_form") => self.form.show()?,
+            _ => return Ok(EventState::NotConsumed),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn edited(&mut self, text: Option<&str>) -> Result<EventState> {
+        edited_pump(text, self.components_mut().as_mut_slice())
+    }
+
     fn is_visible(&self) -> bool {
         self.visible
     }
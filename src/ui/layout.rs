@@ -0,0 +1,427 @@
+use std::cell::{Cell, RefCell};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::components::FocusRing;
+
+/// how a split arranges its children
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum SplitDirection {
+    /// side by side
+    Horizontal,
+    /// on top of each other
+    Vertical,
+}
+
+/// room a child of a split takes up
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum SplitSize {
+    Percentage(u16),
+    Length(u16),
+    Min(u16),
+}
+
+impl SplitSize {
+    const fn constraint(self) -> Constraint {
+        match self {
+            Self::Percentage(p) => Constraint::Percentage(p),
+            Self::Length(l) => Constraint::Length(l),
+            Self::Min(m) => Constraint::Min(m),
+        }
+    }
+}
+
+/// node of a layout tree, panes are named after the component they host
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum LayoutNode {
+    Pane(String),
+    Split {
+        direction: SplitDirection,
+        children: Vec<(SplitSize, LayoutNode)>,
+    },
+}
+
+impl LayoutNode {
+    /// names of all panes, depth first
+    pub fn panes(&self) -> Vec<&str> {
+        match self {
+            Self::Pane(name) => vec![name.as_str()],
+            Self::Split { children, .. } => {
+                children.iter().flat_map(|(_, c)| c.panes()).collect()
+            }
+        }
+    }
+
+    /// checks that only `known` panes are used, each at most once,
+    /// and that every split can actually be laid out
+    pub fn validate(&self, known: &[&str]) -> Result<()> {
+        let panes = self.panes();
+        for (i, pane) in panes.iter().enumerate() {
+            if !known.contains(pane) {
+                bail!(
+                    "unknown pane '{}', expected one of: {}",
+                    pane,
+                    known.join(", ")
+                );
+            }
+            if panes[..i].contains(pane) {
+                bail!("pane '{}' is used more than once", pane);
+            }
+        }
+
+        self.validate_splits()
+    }
+
+    fn validate_splits(&self) -> Result<()> {
+        if let Self::Split { children, .. } = self {
+            if children.is_empty() {
+                bail!("a split needs at least one child");
+            }
+            let percent: u32 = children
+                .iter()
+                .filter_map(|(size, _)| match size {
+                    SplitSize::Percentage(p) => Some(u32::from(*p)),
+                    _ => None,
+                })
+                .sum();
+            if percent > 100 {
+                bail!(
+                    "percentages of a split add up to {}, at most 100 fit",
+                    percent
+                );
+            }
+            for (_, child) in children {
+                child.validate_splits()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn areas(&self, rect: Rect, out: &mut Vec<(String, Rect)>) {
+        match self {
+            Self::Pane(name) => out.push((name.clone(), rect)),
+            Self::Split {
+                direction,
+                children,
+            } => {
+                let chunks = Layout::default()
+                    .direction(match direction {
+                        SplitDirection::Horizontal => Direction::Horizontal,
+                        SplitDirection::Vertical => Direction::Vertical,
+                    })
+                    .constraints(
+                        children
+                            .iter()
+                            .map(|(size, _)| size.constraint())
+                            .collect::<Vec<_>>(),
+                    )
+                    .split(rect);
+
+                for ((_, child), chunk) in children.iter().zip(chunks) {
+                    child.areas(chunk, out);
+                }
+            }
+        }
+    }
+
+    /// the split holding `pane` directly and the index of the pane in it
+    fn parent_of(
+        &mut self,
+        pane: &str,
+    ) -> Option<(&mut Vec<(SplitSize, LayoutNode)>, usize)> {
+        match self {
+            Self::Pane(_) => None,
+            Self::Split { children, .. } => {
+                if let Some(index) = children
+                    .iter()
+                    .position(|(_, c)| matches!(c, Self::Pane(n) if n == pane))
+                {
+                    return Some((children, index));
+                }
+                children.iter_mut().find_map(|(_, c)| c.parent_of(pane))
+            }
+        }
+    }
+}
+
+/// a direction focus can move in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FocusDirection {
+    Left,
+    Right,
+    Above,
+    Below,
+}
+
+/// a layout tree together with the focused pane,
+/// remembers where panes were drawn to move focus geometrically
+pub struct PaneLayout {
+    root: LayoutNode,
+    focus: FocusRing<String>,
+    maximized: bool,
+    areas: RefCell<Vec<(String, Rect)>>,
+    /// all of the last drawn rect, the maximized pane covers it
+    bounds: Cell<Rect>,
+}
+
+impl PaneLayout {
+    /// `focusable` lists the panes that can take focus,
+    /// the first one present in the tree starts focused
+    pub fn new(root: LayoutNode, focusable: &[&str]) -> Self {
+        let panes = root.panes();
+        let focusable: Vec<String> = focusable
+            .iter()
+            .filter(|f| panes.contains(f))
+            .map(|f| (*f).to_string())
+            .collect();
+
+        Self {
+            root,
+            focus: FocusRing::new(focusable),
+            maximized: false,
+            areas: RefCell::new(Vec::new()),
+            bounds: Cell::new(Rect::default()),
+        }
+    }
+
+    /// whether the tree places `pane` at all
+    pub fn contains(&self, pane: &str) -> bool {
+        self.root.panes().contains(&pane)
+    }
+
+    pub fn focused(&self) -> &str {
+        self.focus.current().map_or("", String::as_str)
+    }
+
+    /// focuses `pane` and remembers the previous focus for `focus_back`
+    pub fn push_focus(&mut self, pane: &str) -> bool {
+        self.focus.push(&pane.to_string())
+    }
+
+    pub fn focus_back(&mut self) {
+        self.focus.back();
+    }
+
+    pub fn focus(&mut self, pane: &str) -> bool {
+        self.focus.set(&pane.to_string())
+    }
+
+    /// the focusable pane after or before the focused one
+    pub fn cycled(&self, forward: bool) -> Option<String> {
+        self.focus.peek(forward).cloned()
+    }
+
+    /// the pane drawn at the given cell
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<String> {
+        let contains = |r: &Rect| {
+            column >= r.x
+                && column < r.right()
+                && row >= r.y
+                && row < r.bottom()
+        };
+        if self.maximized {
+            return Some(self.focused().to_string())
+                .filter(|_| contains(&self.bounds.get()));
+        }
+
+        self.areas
+            .borrow()
+            .iter()
+            .find(|(_, r)| contains(r))
+            .map(|(n, _)| n.clone())
+    }
+
+    /// where to draw each pane, a maximized pane gets all of `rect`
+    pub fn areas(&self, rect: Rect) -> Vec<(String, Rect)> {
+        let mut areas = Vec::new();
+        self.root.areas(rect, &mut areas);
+        *self.areas.borrow_mut() = areas.clone();
+        self.bounds.set(rect);
+
+        if self.maximized {
+            vec![(self.focused().to_string(), rect)]
+        } else {
+            areas
+        }
+    }
+
+    pub const fn is_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    pub fn toggle_maximized(&mut self) {
+        self.maximized = !self.maximized;
+    }
+
+    /// the nearest focusable pane in `direction` of the last drawn layout
+    pub fn neighbour(&self, direction: FocusDirection) -> Option<String> {
+        let areas = self.areas.borrow();
+        let current = areas.iter().find(|(n, _)| n == self.focused())?.1;
+
+        let overlaps = |a: u16, a_len: u16, b: u16, b_len: u16| {
+            a < b.saturating_add(b_len) && b < a.saturating_add(a_len)
+        };
+
+        areas
+            .iter()
+            .filter(|(n, _)| n != self.focused() && self.focus.contains(n))
+            .filter_map(|(n, r)| {
+                let distance = match direction {
+                    FocusDirection::Left
+                        if r.right() <= current.x
+                            && overlaps(r.y, r.height, current.y, current.height) =>
+                    {
+                        current.x - r.right()
+                    }
+                    FocusDirection::Right
+                        if r.x >= current.right()
+                            && overlaps(r.y, r.height, current.y, current.height) =>
+                    {
+                        r.x - current.right()
+                    }
+                    FocusDirection::Above
+                        if r.bottom() <= current.y
+                            && overlaps(r.x, r.width, current.x, current.width) =>
+                    {
+                        current.y - r.bottom()
+                    }
+                    FocusDirection::Below
+                        if r.y >= current.bottom()
+                            && overlaps(r.x, r.width, current.x, current.width) =>
+                    {
+                        r.y - current.bottom()
+                    }
+                    _ => return None,
+                };
+                Some((distance, n.clone()))
+            })
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, n)| n)
+    }
+
+    /// grows or shrinks the focused pane inside its split,
+    /// percentages are traded with a neighbour
+    pub fn resize(&mut self, grow: bool) {
+        const STEP: u16 = 5;
+        const MIN_PERCENT: u16 = 10;
+
+        let focus = self.focused().to_string();
+        let (children, index) = match self.root.parent_of(&focus) {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        match children[index].0 {
+            SplitSize::Percentage(p) => {
+                let neighbour = if index + 1 < children.len() {
+                    index + 1
+                } else if index > 0 {
+                    index - 1
+                } else {
+                    return;
+                };
+                if let SplitSize::Percentage(n) = children[neighbour].0 {
+                    let (p, n) = if grow && n >= MIN_PERCENT + STEP {
+                        (p + STEP, n - STEP)
+                    } else if !grow && p >= MIN_PERCENT + STEP {
+                        (p - STEP, n + STEP)
+                    } else {
+                        (p, n)
+                    };
+                    children[index].0 = SplitSize::Percentage(p);
+                    children[neighbour].0 = SplitSize::Percentage(n);
+                }
+            }
+            SplitSize::Length(l) => {
+                children[index].0 = SplitSize::Length(if grow {
+                    l.saturating_add(1)
+                } else {
+                    l.saturating_sub(1).max(1)
+                });
+            }
+            SplitSize::Min(m) => {
+                children[index].0 = SplitSize::Min(if grow {
+                    m.saturating_add(1)
+                } else {
+                    m.saturating_sub(1).max(1)
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tui::layout::Rect;
+
+    use super::{
+        FocusDirection, LayoutNode, PaneLayout, SplitDirection, SplitSize,
+    };
+
+    fn pane(name: &str) -> LayoutNode {
+        LayoutNode::Pane(name.to_string())
+    }
+
+    #[test]
+    fn test_focus_and_resize() {
+        let root = LayoutNode::Split {
+            direction: SplitDirection::Horizontal,
+            children: vec![
+                (SplitSize::Percentage(50), pane("a")),
+                (
+                    SplitSize::Percentage(50),
+                    LayoutNode::Split {
+                        direction: SplitDirection::Vertical,
+                        children: vec![
+                            (SplitSize::Percentage(50), pane("b")),
+                            (SplitSize::Percentage(50), pane("c")),
+                        ],
+                    },
+                ),
+            ],
+        };
+        let mut layout = PaneLayout::new(root, &["a", "b", "c"]);
+        layout.areas(Rect::new(0, 0, 100, 40));
+
+        assert_eq!(layout.neighbour(FocusDirection::Left), None);
+        assert_eq!(
+            layout.neighbour(FocusDirection::Right),
+            Some("b".to_string())
+        );
+        layout.focus("b");
+        assert_eq!(
+            layout.neighbour(FocusDirection::Below),
+            Some("c".to_string())
+        );
+        layout.focus("c");
+        assert_eq!(layout.pane_at(10, 10), Some("a".to_string()));
+
+        layout.resize(true);
+        let areas = layout.areas(Rect::new(0, 0, 100, 40));
+        assert_eq!(areas[2].1.height, 22);
+
+        layout.toggle_maximized();
+        assert_eq!(
+            layout.areas(Rect::new(0, 0, 100, 40)),
+            vec![("c".to_string(), Rect::new(0, 0, 100, 40))]
+        );
+        assert_eq!(layout.pane_at(10, 10), Some("c".to_string()));
+        assert_eq!(layout.pane_at(10, 40), None);
+    }
+
+    #[test]
+    fn test_validate_percent_overflow() {
+        let root = LayoutNode::Split {
+            direction: SplitDirection::Horizontal,
+            children: vec![
+                (SplitSize::Percentage(u16::MAX), pane("a")),
+                (SplitSize::Percentage(u16::MAX), pane("b")),
+            ],
+        };
+
+        assert!(root.validate(&["a", "b"]).is_err());
+    }
+}
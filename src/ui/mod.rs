@@ -1,7 +1,27 @@
-use tui::layout::Rect;
+use std::io::Stdout;
 
+use tui::{
+    backend::CrosstermBackend,
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+pub mod layout;
 pub mod style;
 
+/// backend everything gets drawn to
+pub type UiBackend = CrosstermBackend<Stdout>;
+
+/// dims whatever got drawn to its area so far
+pub struct Dim;
+
+impl Widget for Dim {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+    }
+}
 
 pub fn centered_rect_absolute(
     width: u16,
@@ -18,6 +18,8 @@ use tui::style::{Color, Modifier, Style};
 
 // use asyncgit::{DiffLineType, StatusItemType};
 
+use crate::components::MsgKind;
+
 pub type SharedTheme = Rc<Theme>;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -213,6 +215,15 @@ impl Theme {
         Style::default().fg(self.danger_fg)
     }
 
+    pub fn notification(&self, kind: MsgKind) -> Style {
+        match kind {
+            MsgKind::Error => self.text_danger(),
+            MsgKind::Warning => Style::default().fg(self.diff_file_modified),
+            MsgKind::Info => Style::default().fg(self.command_fg),
+            MsgKind::Success => Style::default().fg(self.diff_line_add),
+        }
+    }
+
     pub fn commandbar(&self, enabled: bool, line: usize) -> Style {
         if enabled {
             Style::default().fg(self.command_fg)